@@ -0,0 +1,198 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use lib_simulation as sim;
+use std::error::Error;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+// Headless counterpart of the browser app, for training on servers
+
+#[derive(Parser)]
+#[clap(name = "evol-sim", version, about)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Evolve a population for a number of generations
+    Train {
+        /// Generations to evolve for
+        #[clap(short, long, default_value_t = 100)]
+        generations: usize,
+
+        /// Simulation config (TOML, or JSON with a `.json` extension)
+        #[clap(short, long)]
+        config: Option<PathBuf>,
+
+        /// Seed of a new simulation; random if not given
+        #[clap(short, long, conflicts_with = "resume")]
+        seed: Option<u64>,
+
+        /// Carry on from a checkpoint instead of starting over
+        #[clap(short, long, conflicts_with = "config")]
+        resume: Option<PathBuf>,
+
+        /// Where to write the checkpoint once done
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Score the hall of fame of a checkpoint in a fresh world
+    Eval {
+        checkpoint: PathBuf,
+
+        /// Seed of the world the brains are scored in
+        #[clap(short, long, default_value_t = 0)]
+        seed: u64,
+
+        /// Config of that world; the checkpoint's one if not given
+        #[clap(short, long)]
+        config: Option<PathBuf>,
+    },
+
+    /// Print the statistics of every generation of a checkpoint
+    Stats {
+        checkpoint: PathBuf,
+
+        #[clap(short, long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Train { generations, config, seed, resume, output } => {
+            let mut simulation = match resume {
+                Some(path) => sim::Simulation::load_checkpoint(path)?,
+                None => {
+                    let config = load_config(config)?;
+                    let seed = seed.unwrap_or_else(rand::random);
+
+                    sim::Simulation::with_config(seed, config)?
+                }
+            };
+
+            println!("seed {}", simulation.seed());
+
+            for _ in 0..generations {
+                let generation = simulation.generation();
+                let stats = simulation.train();
+
+                println!(
+                    "generation {:>5}: min={:.2}, max={:.2}, avg={:.2}, diversity={:.3}",
+                    generation,
+                    stats.min_fitness(),
+                    stats.max_fitness(),
+                    stats.avg_fitness(),
+                    stats.diversity(),
+                );
+            }
+
+            if let Some(path) = output {
+                simulation.save_checkpoint(&path)?;
+                println!("checkpoint saved to {}", path.display());
+            }
+        }
+
+        Command::Eval { checkpoint, seed, config } => {
+            let simulation = sim::Simulation::load_checkpoint(checkpoint)?;
+
+            let config = match config {
+                Some(path) => sim::SimulationConfig::load(path)?,
+                None => simulation.config().clone(),
+            };
+
+            let entries = simulation.hall_of_fame().entries();
+
+            if entries.is_empty() {
+                return Err("checkpoint has no hall of fame yet - train it for a generation first".into());
+            }
+
+            let brains: Vec<_> = entries
+                .iter()
+                .map(|entry| entry.chromosome.clone())
+                .collect();
+
+            let scores = sim::Simulation::evaluate(seed, config, &brains)?;
+
+            println!("{:>4} {:>10} {:>8} {:>8}", "rank", "generation", "fitness", "score");
+
+            for (rank, (entry, score)) in entries.iter().zip(scores).enumerate() {
+                println!(
+                    "{:>4} {:>10} {:>8.2} {:>8.2}",
+                    rank + 1,
+                    entry.generation,
+                    entry.fitness,
+                    score,
+                );
+            }
+        }
+
+        Command::Stats { checkpoint, format } => {
+            let simulation = sim::Simulation::load_checkpoint(checkpoint)?;
+            let history = simulation.history();
+
+            match format {
+                Format::Json => println!("{}", serde_json::to_string_pretty(history)?),
+                Format::Csv | Format::Table => {
+                    let csv = matches!(format, Format::Csv);
+
+                    if csv {
+                        println!("generation,min_fitness,max_fitness,avg_fitness,diversity");
+                    } else {
+                        println!("{:>10} {:>8} {:>8} {:>8} {:>9}", "generation", "min", "max", "avg", "diversity");
+                    }
+
+                    for (generation, stats) in history.iter().enumerate() {
+                        if csv {
+                            println!(
+                                "{},{},{},{},{}",
+                                generation,
+                                stats.min_fitness(),
+                                stats.max_fitness(),
+                                stats.avg_fitness(),
+                                stats.diversity(),
+                            );
+                        } else {
+                            println!(
+                                "{:>10} {:>8.2} {:>8.2} {:>8.2} {:>9.3}",
+                                generation,
+                                stats.min_fitness(),
+                                stats.max_fitness(),
+                                stats.avg_fitness(),
+                                stats.diversity(),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn load_config(path: Option<PathBuf>) -> Result<sim::SimulationConfig, sim::ConfigError> {
+    match path {
+        Some(path) => sim::SimulationConfig::load(path),
+        None => Ok(sim::SimulationConfig::default()),
+    }
+}
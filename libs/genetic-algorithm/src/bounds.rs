@@ -0,0 +1,173 @@
+use crate::*;
+
+// How a gene that ended up outside its bounds is brought back in
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Repair {
+    // Moved to the nearest bound
+    Clamp,
+
+    // Bounced off the bound it crossed, by as much as it overshot
+    Reflect,
+
+    // Re-enters from the opposite bound, as if the range was circular
+    Wrap,
+}
+
+impl Repair {
+    pub fn apply(self, value: f32, min: f32, max: f32) -> f32 {
+        if (min..=max).contains(&value) {
+            return value;
+        }
+
+        let width = max - min;
+
+        if width == 0.0 {
+            return min;
+        }
+
+        let value = match self {
+            Self::Clamp => value,
+            Self::Reflect => {
+                let offset = (value - min).rem_euclid(2.0 * width);
+
+                if offset > width {
+                    max - (offset - width)
+                } else {
+                    min + offset
+                }
+            }
+            Self::Wrap => min + (value - min).rem_euclid(width),
+        };
+
+        // Rounding can leave the result a hair outside the range
+        value.clamp(min, max)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Ranges {
+    Uniform(f32, f32),
+    PerGene(Vec<(f32, f32)>),
+}
+
+// Allowed range of every gene of a chromosome, either the same for all of
+// them or one per gene.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneBounds {
+    ranges: Ranges,
+    repair: Repair,
+}
+
+impl GeneBounds {
+    pub fn uniform(min: f32, max: f32, repair: Repair) -> Self {
+        assert!(min <= max);
+
+        Self { ranges: Ranges::Uniform(min, max), repair }
+    }
+
+    pub fn per_gene(ranges: Vec<(f32, f32)>, repair: Repair) -> Self {
+        assert!(ranges.iter().all(|(min, max)| min <= max));
+
+        Self { ranges: Ranges::PerGene(ranges), repair }
+    }
+
+    pub fn range(&self, idx: usize) -> (f32, f32) {
+        match &self.ranges {
+            Ranges::Uniform(min, max) => (*min, *max),
+            Ranges::PerGene(ranges) => ranges[idx],
+        }
+    }
+
+    pub fn repair(&self, chromosome: &mut Chromosome) {
+        if let Ranges::PerGene(ranges) = &self.ranges {
+            assert_eq!(ranges.len(), chromosome.len());
+        }
+
+        for (idx, gene) in chromosome.iter_mut().enumerate() {
+            let (min, max) = self.range(idx);
+
+            *gene = self.repair.apply(*gene, min, max);
+        }
+    }
+}
+
+// Runs another mutation method and repairs whatever genes it pushed out of
+// `bounds`; tunable parameters are the wrapped method's.
+
+#[derive(Clone, Debug)]
+pub struct BoundedMutation<M> {
+    mutation: M,
+    bounds: GeneBounds,
+}
+
+impl<M> BoundedMutation<M> {
+    pub fn new(mutation: M, bounds: GeneBounds) -> Self {
+        Self { mutation, bounds }
+    }
+
+    pub fn bounds(&self) -> &GeneBounds {
+        &self.bounds
+    }
+}
+
+impl<M> MutationMethod for BoundedMutation<M>
+where
+    M: MutationMethod,
+{
+    fn mutate(
+        &self,
+        rng: &mut dyn RngCore,
+        child: &mut Chromosome
+    ) {
+        self.mutation.mutate(rng, child);
+        self.bounds.repair(child);
+    }
+
+    fn params(&self) -> Option<MutationParams> {
+        self.mutation.params()
+    }
+
+    fn set_params(&mut self, params: MutationParams) {
+        self.mutation.set_params(params);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn repair_strategies() {
+        approx::assert_relative_eq!(Repair::Clamp.apply(1.5, -1.0, 1.0), 1.0);
+        approx::assert_relative_eq!(Repair::Reflect.apply(1.5, -1.0, 1.0), 0.5);
+        approx::assert_relative_eq!(Repair::Reflect.apply(-3.5, -1.0, 1.0), 0.5);
+        approx::assert_relative_eq!(Repair::Wrap.apply(1.5, -1.0, 1.0), -0.5);
+        approx::assert_relative_eq!(Repair::Wrap.apply(-1.5, -1.0, 1.0), 0.5);
+        approx::assert_relative_eq!(Repair::Wrap.apply(0.25, -1.0, 1.0), 0.25);
+    }
+
+    #[test]
+    fn bounded_mutation_keeps_genes_in_range() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let bounds = GeneBounds::per_gene(vec![(0.0, 1.0), (-0.1, 0.1), (5.0, 5.0)], Repair::Reflect);
+        let mutation = BoundedMutation::new(GaussianMutation::new(1.0, 10.0), bounds.clone());
+
+        for _ in 0..100 {
+            let mut child: Chromosome = vec![0.5, 0.0, 5.0].into_iter().collect();
+
+            mutation.mutate(&mut rng, &mut child);
+
+            for (idx, gene) in child.iter().enumerate() {
+                let (min, max) = bounds.range(idx);
+                assert!((min..=max).contains(gene));
+            }
+        }
+
+        assert_eq!(mutation.params(), GaussianMutation::new(1.0, 10.0).params());
+    }
+}
@@ -0,0 +1,194 @@
+use crate::*;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use rand_chacha::ChaCha8Rng;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointEntry<G = f32> {
+    pub chromosome: Chromosome<G>,
+    pub fitness: f32,
+    pub lineage: Option<Lineage>,
+}
+
+// Settings of the `GeneticAlgorithm` that change while it runs; the
+// operators themselves aren't saved, so a checkpoint has to be restored
+// into a `GeneticAlgorithm` built the same way as the one it came from.
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GaConfig {
+    pub rates: OperatorRates,
+    pub reproduction: Reproduction,
+
+    // See `RateController::state`
+    pub rate_controller: Option<serde_json::Value>,
+}
+
+// Everything needed to pick up evolution where it was left: the population,
+// the generation counter, the rng and the GA's state, rate controller
+// included.
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Checkpoint<G = f32> {
+    pub generation: usize,
+    pub population: Vec<CheckpointEntry<G>>,
+    pub rng: ChaCha8Rng,
+    pub config: GaConfig,
+    next_id: u64,
+    genealogy: Option<Genealogy>,
+}
+
+impl<G> Checkpoint<G>
+where
+    G: Gene,
+{
+    pub fn new<S, I>(ga: &GeneticAlgorithm<S, G>, population: &[I], rng: &ChaCha8Rng) -> Self
+    where
+        S: SelectionMethod,
+        I: Individual<G>,
+    {
+        let population = population
+            .iter()
+            .map(|indiv| CheckpointEntry {
+                chromosome: indiv.chromosome().clone(),
+                fitness: indiv.fitness(),
+                lineage: indiv.lineage().cloned(),
+            })
+            .collect();
+
+        Self {
+            generation: ga.generation,
+            population,
+            rng: rng.clone(),
+            config: GaConfig {
+                rates: ga.rates(),
+                reproduction: ga.reproduction,
+                rate_controller: ga.rate_controller.as_ref().and_then(|controller| controller.state()),
+            },
+            next_id: ga.next_id,
+            genealogy: ga.genealogy.clone(),
+        }
+    }
+
+    // Puts `ga` back in the state it was in when the checkpoint was taken
+    // and returns the population; fitness isn't restored, since individuals
+    // are rebuilt through `Individual::create` - use `population` directly
+    // if it's needed. Fails if the rate controller's state doesn't fit the
+    // controller `ga` has.
+
+    pub fn restore<S, I>(&self, ga: &mut GeneticAlgorithm<S, G>) -> io::Result<Vec<I>>
+    where
+        S: SelectionMethod,
+        I: Individual<G>,
+    {
+        ga.generation = self.generation;
+        ga.next_id = self.next_id;
+        ga.genealogy = self.genealogy.clone();
+        ga.reproduction = self.config.reproduction;
+        ga.set_rates(self.config.rates);
+
+        if let (Some(controller), Some(state)) = (&mut ga.rate_controller, &self.config.rate_controller) {
+            controller.restore_state(state.clone())?;
+        }
+
+        let population = self.population
+            .iter()
+            .map(|entry| {
+                let mut indiv = I::create(entry.chromosome.clone());
+
+                if let Some(lineage) = &entry.lineage {
+                    indiv.set_lineage(lineage.clone());
+                }
+
+                indiv
+            })
+            .collect();
+
+        Ok(population)
+    }
+}
+
+impl<G> Checkpoint<G>
+where
+    G: Serialize + for<'de> Deserialize<'de>,
+{
+    pub fn save(&self, writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    pub fn load(reader: impl Read) -> io::Result<Self> {
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        self.save(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load_from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::load(BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn new_ga() -> GeneticAlgorithm<RouletteWheelSelection> {
+        GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_crossover_rate(0.8)
+        .with_genealogy()
+    }
+
+    fn fingerprint(population: &[TestIndiv]) -> Vec<Vec<f32>> {
+        population
+            .iter()
+            .map(|indiv| indiv.chromosome().iter().copied().collect())
+            .collect()
+    }
+
+    #[test]
+    fn resuming_continues_the_same_run() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = new_ga();
+
+        let mut population: Vec<_> = (0..6)
+            .map(|idx| TestIndiv::create([idx as f32, 1.0, 2.0].into_iter().collect()))
+            .collect();
+
+        for _ in 0..3 {
+            population = ga.iterate(&population, &mut rng).0;
+        }
+
+        let mut saved = Vec::new();
+        Checkpoint::new(&ga, &population, &rng).save(&mut saved).unwrap();
+
+        for _ in 0..3 {
+            population = ga.iterate(&population, &mut rng).0;
+        }
+
+        let checkpoint = Checkpoint::load(saved.as_slice()).unwrap();
+        let mut resumed_ga = new_ga();
+        let mut resumed: Vec<TestIndiv> = checkpoint.restore(&mut resumed_ga).unwrap();
+        let mut resumed_rng = checkpoint.rng.clone();
+
+        assert_eq!(resumed_ga.generation(), 3);
+        assert_eq!(resumed_ga.rates(), ga.rates());
+
+        for _ in 0..3 {
+            resumed = resumed_ga.iterate(&resumed, &mut resumed_rng).0;
+        }
+
+        assert_eq!(fingerprint(&resumed), fingerprint(&population));
+        assert_eq!(resumed_ga.genealogy().unwrap().len(), ga.genealogy().unwrap().len());
+    }
+}
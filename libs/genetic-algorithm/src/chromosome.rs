@@ -0,0 +1,101 @@
+use crate::*;
+
+// Numeric view of a gene, used to measure how diverse a population is;
+// implement it for your own gene types (e.g. enums) to evolve them.
+
+pub trait Gene: Clone + PartialEq + MaybeSendSync {
+    fn value(&self) -> f32;
+}
+
+impl Gene for f32 {
+    fn value(&self) -> f32 {
+        *self
+    }
+}
+
+impl Gene for bool {
+    fn value(&self) -> f32 {
+        if *self { 1.0 } else { 0.0 }
+    }
+}
+
+macro_rules! impl_gene_for_integers {
+    ($($ty:ty),*) => {
+        $(
+            impl Gene for $ty {
+                fn value(&self) -> f32 {
+                    *self as f32
+                }
+            }
+        )*
+    };
+}
+
+impl_gene_for_integers!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
+
+// Genes default to `f32` (neural network weights), but other types work
+// too: `bool` for binary encodings, integers for discrete parameters and
+// `usize` indices for permutations.
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Chromosome<G = f32> {
+    genes: Vec<G>,
+}
+
+impl<G> Chromosome<G> {
+    pub fn len(&self) -> usize {
+        self.genes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.genes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &G> {
+        self.genes.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut G> {
+        self.genes.iter_mut()
+    }
+
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.genes.swap(a, b);
+    }
+}
+
+impl<G> Index<usize> for Chromosome<G> {
+    type Output = G;
+
+    fn index(&self, index:usize) -> &Self::Output {
+        &self.genes[index]
+    }
+}
+
+impl<G> FromIterator<G> for Chromosome<G> {
+    fn from_iter<T: IntoIterator<Item = G>> (iter: T) -> Self {
+        Self {
+            genes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<G> IntoIterator for Chromosome<G> {
+    type Item = G;
+    type IntoIter = std::vec::IntoIter<G>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.genes.into_iter()
+    }
+}
+
+#[cfg(test)]
+impl PartialEq for Chromosome {
+
+    fn eq(&self, other: &Self) -> bool {
+        approx::relative_eq!(
+             self.genes.as_slice(),
+             other.genes.as_slice(),
+        )
+    }
+}
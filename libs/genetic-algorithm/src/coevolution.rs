@@ -0,0 +1,422 @@
+use crate::*;
+
+// Who an individual is evaluated against in each of the other populations
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pairing {
+    // Every individual against every member of the other populations
+    AllVsAll,
+
+    // Against this many randomly picked members of each other population
+    RandomSample(usize),
+
+    // Against the best this many individuals each other population has
+    // produced so far; until there are any, against a random sample of the
+    // same size.
+    HallOfFame(usize),
+}
+
+// One meeting between two individuals of different populations; the
+// evaluation function scores both of them.
+
+#[derive(Clone, Copy, Debug)]
+pub struct Encounter<'a, G = f32> {
+    pub species: [usize; 2],
+    pub chromosomes: [&'a Chromosome<G>; 2],
+
+    // Where the chromosomes are in their species' population, or in its
+    // hall of fame for hall of famers
+    pub individuals: [usize; 2],
+}
+
+// A chromosome together with the fitness its encounters earned it
+
+#[derive(Clone, Debug)]
+pub struct Scored<G = f32> {
+    chromosome: Chromosome<G>,
+    fitness: f32,
+    lineage: Option<Lineage>,
+}
+
+impl<G> Individual<G> for Scored<G> {
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        &self.chromosome
+    }
+
+    fn create(chromosome: Chromosome<G>) -> Self {
+        Self { chromosome, fitness: 0.0, lineage: None }
+    }
+
+    fn lineage(&self) -> Option<&Lineage> {
+        self.lineage.as_ref()
+    }
+
+    fn set_lineage(&mut self, lineage: Lineage) {
+        self.lineage = Some(lineage);
+    }
+}
+
+struct Species<S, G> {
+    ga: GeneticAlgorithm<S, G>,
+    population: Vec<Chromosome<G>>,
+    lineages: Vec<Option<Lineage>>,
+    hall_of_fame: HallOfFame<G>,
+}
+
+// Evolves several populations, each with its own `GeneticAlgorithm`, whose
+// fitness comes from encounters with the others (e.g. predators and prey).
+// An individual's fitness is the average score of its encounters, so scores
+// must not be negative.
+
+pub struct CoEvolution<S, G = f32> {
+    species: Vec<Species<S, G>>,
+    pairing: Pairing,
+}
+
+impl<S, G> CoEvolution<S, G>
+where
+    S: SelectionMethod + MaybeSendSync,
+    G: Gene,
+{
+    pub fn new(pairing: Pairing) -> Self {
+        if let Pairing::RandomSample(opponents) | Pairing::HallOfFame(opponents) = pairing {
+            assert!(opponents > 0);
+        }
+
+        Self { species: Vec::new(), pairing }
+    }
+
+    // Species are identified by the order they're added in, starting at 0.
+    // The population may be left empty and filled in with `set_population`
+    // before the first step.
+
+    pub fn with_species(mut self, ga: GeneticAlgorithm<S, G>, population: Vec<Chromosome<G>>) -> Self {
+        let capacity = match self.pairing {
+            Pairing::HallOfFame(opponents) => opponents,
+            _ => 1,
+        };
+
+        self.species.push(Species {
+            ga,
+            lineages: vec![None; population.len()],
+            population,
+            hall_of_fame: HallOfFame::new(capacity),
+        });
+
+        self
+    }
+
+    pub fn pairing(&self) -> Pairing {
+        self.pairing
+    }
+
+    pub fn species(&self) -> usize {
+        self.species.len()
+    }
+
+    pub fn population(&self, species: usize) -> &[Chromosome<G>] {
+        &self.species[species].population
+    }
+
+    // Lineage of each member of `population`, see `Individual::lineage`
+
+    pub fn lineages(&self, species: usize) -> &[Option<Lineage>] {
+        &self.species[species].lineages
+    }
+
+    // Replaces the population of `species`, for when its members live
+    // somewhere else between steps (e.g. in a simulated world)
+
+    pub fn set_population<I>(&mut self, species: usize, population: &[I])
+    where
+        I: Individual<G>,
+    {
+        let species = &mut self.species[species];
+
+        species.population = population
+            .iter()
+            .map(|indiv| indiv.chromosome().clone())
+            .collect();
+
+        species.lineages = population
+            .iter()
+            .map(|indiv| indiv.lineage().cloned())
+            .collect();
+    }
+
+    pub fn ga(&self, species: usize) -> &GeneticAlgorithm<S, G> {
+        &self.species[species].ga
+    }
+
+    pub fn ga_mut(&mut self, species: usize) -> &mut GeneticAlgorithm<S, G> {
+        &mut self.species[species].ga
+    }
+
+    // Best individuals of `species` so far; as many as the `HallOfFame`
+    // pairing asks for, or just the best one otherwise
+
+    pub fn hall_of_fame(&self, species: usize) -> &HallOfFame<G> {
+        &self.species[species].hall_of_fame
+    }
+
+    // Scores every population against the others without evolving them
+
+    pub fn evaluate(
+        &self,
+        rng: &mut dyn RngCore,
+        mut evaluate: impl FnMut(&Encounter<G>) -> (f32, f32),
+    ) -> Vec<Vec<Scored<G>>> {
+        assert!(self.species.len() >= 2);
+
+        let mut totals: Vec<Vec<(f32, usize)>> = self
+            .species
+            .iter()
+            .map(|species| vec![(0.0, 0); species.population.len()])
+            .collect();
+
+        fn credit(totals: &mut [Vec<(f32, usize)>], species: usize, idx: usize, score: f32) {
+            let total = &mut totals[species][idx];
+            total.0 += score;
+            total.1 += 1;
+        }
+
+        match self.pairing {
+            Pairing::AllVsAll => {
+                for a in 0..self.species.len() {
+                    for b in (a + 1)..self.species.len() {
+                        for (i, chromosome_a) in self.species[a].population.iter().enumerate() {
+                            for (j, chromosome_b) in self.species[b].population.iter().enumerate() {
+                                let (score_a, score_b) = evaluate(&Encounter {
+                                    species: [a, b],
+                                    chromosomes: [chromosome_a, chromosome_b],
+                                    individuals: [i, j],
+                                });
+
+                                credit(&mut totals, a, i, score_a);
+                                credit(&mut totals, b, j, score_b);
+                            }
+                        }
+                    }
+                }
+            }
+
+            Pairing::RandomSample(opponents) | Pairing::HallOfFame(opponents) => {
+                for a in 0..self.species.len() {
+                    for (i, chromosome_a) in self.species[a].population.iter().enumerate() {
+                        for b in (0..self.species.len()).filter(|&b| b != a) {
+                            let hall_of_fame = &self.species[b].hall_of_fame;
+
+                            if let (Pairing::HallOfFame(_), false) = (self.pairing, hall_of_fame.is_empty()) {
+                                // Hall of famers aren't part of the population
+                                // anymore, so only `a` gets the score
+                                for (j, entry) in hall_of_fame.entries().iter().enumerate() {
+                                    let (score_a, _) = evaluate(&Encounter {
+                                        species: [a, b],
+                                        chromosomes: [chromosome_a, &entry.chromosome],
+                                        individuals: [i, j],
+                                    });
+
+                                    credit(&mut totals, a, i, score_a);
+                                }
+
+                                continue;
+                            }
+
+                            let population = &self.species[b].population;
+                            let sampled = rand::seq::index::sample(
+                                rng,
+                                population.len(),
+                                opponents.min(population.len()),
+                            );
+
+                            for j in sampled {
+                                let (score_a, score_b) = evaluate(&Encounter {
+                                    species: [a, b],
+                                    chromosomes: [chromosome_a, &population[j]],
+                                    individuals: [i, j],
+                                });
+
+                                credit(&mut totals, a, i, score_a);
+                                credit(&mut totals, b, j, score_b);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        self.species
+            .iter()
+            .zip(totals)
+            .map(|(species, totals)| {
+                species
+                    .population
+                    .iter()
+                    .zip(&species.lineages)
+                    .zip(totals)
+                    .map(|((chromosome, lineage), (total, encounters))| Scored {
+                        chromosome: chromosome.clone(),
+                        fitness: if encounters == 0 { 0.0 } else { total / encounters as f32 },
+                        lineage: lineage.clone(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Evaluates all the populations and replaces each of them with its
+    // next generation; returns the statistics of the evaluated ones.
+    // Lineages are kept track of, so the species' genealogies work too.
+
+    pub fn step(
+        &mut self,
+        rng: &mut dyn RngCore,
+        evaluate: impl FnMut(&Encounter<G>) -> (f32, f32),
+    ) -> Vec<Statistics> {
+        let scored = self.evaluate(rng, evaluate);
+
+        self.species
+            .iter_mut()
+            .zip(scored)
+            .map(|(species, mut scored)| {
+                species.ga.assign_lineage(&mut scored);
+                species.hall_of_fame.update(&scored, species.ga.generation());
+
+                let (children, stats) = species.ga.iterate(&scored, rng);
+
+                species.lineages = children
+                    .iter()
+                    .map(|child| child.lineage.clone())
+                    .collect();
+
+                species.population = children
+                    .into_iter()
+                    .map(|child| child.chromosome)
+                    .collect();
+
+                stats
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn coevolution(pairing: Pairing) -> CoEvolution<RouletteWheelSelection> {
+        let ga = || {
+            GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.5, 0.5),
+            )
+        };
+
+        let population = |size: usize| {
+            (0..size)
+                .map(|idx| [idx as f32].into_iter().collect())
+                .collect()
+        };
+
+        CoEvolution::new(pairing)
+            .with_species(ga(), population(4))
+            .with_species(ga(), population(3))
+    }
+
+    // Whoever has the bigger gene wins
+    fn duel(encounter: &Encounter) -> (f32, f32) {
+        let [a, b] = encounter.chromosomes;
+
+        if a[0] >= b[0] { (1.0, 0.1) } else { (0.1, 1.0) }
+    }
+
+    #[test]
+    fn all_vs_all_meets_every_pair_once() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut encounters = 0;
+
+        let scored = coevolution(Pairing::AllVsAll).evaluate(&mut rng, |encounter| {
+            encounters += 1;
+            duel(encounter)
+        });
+
+        assert_eq!(encounters, 12);
+
+        // Gene 3 of the first species beats all of the second one's 0..=2
+        approx::assert_relative_eq!(scored[0][3].fitness(), 1.0);
+        approx::assert_relative_eq!(scored[1][0].fitness(), 0.1);
+    }
+
+    #[test]
+    fn random_sample_limits_encounters() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut encounters = 0;
+
+        coevolution(Pairing::RandomSample(2)).evaluate(&mut rng, |encounter| {
+            encounters += 1;
+            duel(encounter)
+        });
+
+        assert_eq!(encounters, (4 + 3) * 2);
+    }
+
+    #[test]
+    fn hall_of_fame_opponents_come_from_past_generations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut coevolution = coevolution(Pairing::HallOfFame(2));
+
+        let stats = coevolution.step(&mut rng, duel);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(coevolution.ga(0).generation(), 1);
+        assert_eq!(coevolution.hall_of_fame(1).len(), 2);
+
+        let champions: Vec<f32> = coevolution
+            .hall_of_fame(1)
+            .entries()
+            .iter()
+            .map(|entry| entry.chromosome[0])
+            .collect();
+
+        coevolution.step(&mut rng, |encounter| {
+            if encounter.species == [0, 1] {
+                assert!(champions.contains(&encounter.chromosomes[1][0]));
+            }
+
+            duel(encounter)
+        });
+    }
+
+    #[test]
+    fn populations_can_be_replaced_between_steps() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut coevolution = coevolution(Pairing::AllVsAll);
+
+        coevolution.step(&mut rng, duel);
+
+        let lineage = coevolution.lineages(1)[0].clone().unwrap();
+        assert_eq!(lineage.generation, 1);
+
+        let survivor = Scored {
+            chromosome: coevolution.population(1)[0].clone(),
+            fitness: 0.0,
+            lineage: Some(lineage.clone()),
+        };
+
+        coevolution.set_population(1, &[survivor]);
+        coevolution.step(&mut rng, |encounter| {
+            assert_eq!(encounter.individuals[1], 0);
+            duel(encounter)
+        });
+
+        assert_eq!(coevolution.population(1).len(), 1);
+        assert!(coevolution.lineages(1)[0].as_ref().unwrap().parents.contains(&lineage.id));
+    }
+}
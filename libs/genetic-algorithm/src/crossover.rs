@@ -0,0 +1,180 @@
+use crate::*;
+
+pub trait CrossoverMethod<G = f32> {
+     fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+     ) -> Chromosome<G>;
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UniformCrossover;
+
+impl UniformCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+impl<G> CrossoverMethod<G> for UniformCrossover
+where
+    G: Clone,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+     ) -> Chromosome<G> {
+
+        assert_eq!(parent_a.len(), parent_b.len()); //precondition
+
+        let parent_a_iter = parent_a.iter();
+        let parent_b_iter = parent_b.iter();
+
+        const RATIO:f64 = 0.5;
+        parent_a_iter
+            .zip(parent_b_iter)
+            .map(|(a, b)| if rng.gen_bool(RATIO) {a.clone()} else {b.clone()} )
+            .collect()
+        }
+}
+
+// Takes the genes before a random cut point from `parent_a` and the rest
+// from `parent_b`; keeps neighbouring genes together, which suits binary
+// and integer encodings where adjacent genes describe the same trait.
+
+#[derive(Clone, Debug, Default)]
+pub struct SinglePointCrossover;
+
+impl SinglePointCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G> CrossoverMethod<G> for SinglePointCrossover
+where
+    G: Clone,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+     ) -> Chromosome<G> {
+
+        assert_eq!(parent_a.len(), parent_b.len()); //precondition
+
+        let point = rng.gen_range(0..=parent_a.len());
+
+        parent_a
+            .iter()
+            .take(point)
+            .chain(parent_b.iter().skip(point))
+            .cloned()
+            .collect()
+    }
+}
+
+// Order crossover (OX1) for permutation genes: copies a random slice of
+// `parent_a` and fills the remaining positions with the missing genes in
+// the order they appear in `parent_b`, so the child is a permutation too.
+
+#[derive(Clone, Debug, Default)]
+pub struct OrderCrossover;
+
+impl OrderCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CrossoverMethod<usize> for OrderCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<usize>,
+        parent_b: &Chromosome<usize>,
+     ) -> Chromosome<usize> {
+
+        assert_eq!(parent_a.len(), parent_b.len()); //precondition
+
+        let len = parent_a.len();
+
+        if len == 0 {
+            return parent_a.clone();
+        }
+
+        let mut start = rng.gen_range(0..len);
+        let mut end = rng.gen_range(0..len);
+
+        if start > end {
+            std::mem::swap(&mut start, &mut end);
+        }
+
+        let slice: Vec<_> = parent_a
+            .iter()
+            .skip(start)
+            .take(end - start + 1)
+            .collect();
+
+        let mut rest = parent_b
+            .iter()
+            .filter(|gene| !slice.contains(gene));
+
+        (0..len)
+            .map(|idx| {
+                if (start..=end).contains(&idx) {
+                    parent_a[idx]
+                } else {
+                    *rest.next().expect("parents are not permutations of each other")
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn single_point_keeps_a_prefix_and_a_suffix() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome<bool> = vec![true; 8].into_iter().collect();
+        let parent_b: Chromosome<bool> = vec![false; 8].into_iter().collect();
+
+        for _ in 0..20 {
+            let child = SinglePointCrossover::new()
+                .crossover(&mut rng, &parent_a, &parent_b);
+
+            let genes: Vec<_> = child.into_iter().collect();
+            let point = genes.iter().take_while(|gene| **gene).count();
+
+            assert!(genes[point..].iter().all(|gene| !gene));
+        }
+    }
+
+    #[test]
+    fn order_crossover_produces_permutations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome<usize> = (0..10).collect();
+        let parent_b: Chromosome<usize> = (0..10).rev().collect();
+
+        for _ in 0..20 {
+            let child = OrderCrossover::new()
+                .crossover(&mut rng, &parent_a, &parent_b);
+
+            let mut genes: Vec<_> = child.into_iter().collect();
+            genes.sort_unstable();
+
+            assert_eq!(genes, (0..10).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use crate::*;
+
+// When a `Driver` should stop evolving; conditions are checked after every
+// generation against the statistics gathered so far and can be combined
+// with `Termination::Any` / `Termination::All` (or `or` / `and`).
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Termination {
+    MaxGenerations(usize),
+
+    // Best fitness of a generation reached the target
+    TargetFitness(f32),
+
+    // Best fitness hasn't improved for this many generations
+    Stagnation(usize),
+
+    WallClock(Duration),
+
+    // Population diversity (see `Statistics::diversity`) dropped below this
+    DiversityCollapse(f32),
+
+    Any(Vec<Termination>),
+    All(Vec<Termination>),
+}
+
+impl Termination {
+    pub fn or(self, other: Termination) -> Self {
+        match self {
+            Self::Any(mut conditions) => {
+                conditions.push(other);
+                Self::Any(conditions)
+            }
+            this => Self::Any(vec![this, other]),
+        }
+    }
+
+    pub fn and(self, other: Termination) -> Self {
+        match self {
+            Self::All(mut conditions) => {
+                conditions.push(other);
+                Self::All(conditions)
+            }
+            this => Self::All(vec![this, other]),
+        }
+    }
+
+    // Returns the condition that's been met, if any - for `Any` that's the
+    // first of its conditions to be met, for `All` it's the whole group.
+
+    pub fn check(&self, history: &[Statistics], elapsed: Duration) -> Option<&Termination> {
+        let last = history.last()?;
+
+        let met = match self {
+            Self::MaxGenerations(generations) => history.len() >= *generations,
+            Self::TargetFitness(target) => last.max_fitness() >= *target,
+            Self::Stagnation(generations) => {
+                if history.len() <= *generations {
+                    false
+                } else {
+                    let (before, recent) = history.split_at(history.len() - generations);
+
+                    let best = |stats: &[Statistics]| {
+                        stats
+                            .iter()
+                            .map(Statistics::max_fitness)
+                            .fold(f32::NEG_INFINITY, f32::max)
+                    };
+
+                    best(recent) <= best(before)
+                }
+            }
+            Self::WallClock(budget) => elapsed >= *budget,
+            Self::DiversityCollapse(threshold) => last.diversity() < *threshold,
+            Self::Any(conditions) => {
+                return conditions
+                    .iter()
+                    .find_map(|condition| condition.check(history, elapsed));
+            }
+            Self::All(conditions) => {
+                !conditions.is_empty() && conditions
+                    .iter()
+                    .all(|condition| condition.check(history, elapsed).is_some())
+            }
+        };
+
+        if met { Some(self) } else { None }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RunReport<I> {
+    // Last population, the one `history.last()` describes
+    pub population: Vec<I>,
+    pub history: Vec<Statistics>,
+    pub stopped_by: Termination,
+    pub elapsed: Duration,
+}
+
+impl<I> RunReport<I> {
+    pub fn generations(&self) -> usize {
+        self.history.len()
+    }
+}
+
+// Runs a `GeneticAlgorithm` generation after generation until its
+// `Termination` is met.
+
+pub struct Driver<S, G = f32> {
+    ga: GeneticAlgorithm<S, G>,
+    termination: Termination,
+}
+
+impl<S, G> Driver<S, G>
+where
+    S: SelectionMethod + MaybeSendSync,
+    G: Gene,
+{
+    pub fn new(ga: GeneticAlgorithm<S, G>, termination: Termination) -> Self {
+        Self { ga, termination }
+    }
+
+    pub fn ga(&self) -> &GeneticAlgorithm<S, G> {
+        &self.ga
+    }
+
+    pub fn termination(&self) -> &Termination {
+        &self.termination
+    }
+
+    // For individuals whose fitness follows from their chromosome alone
+
+    pub fn run<I>(&mut self, population: Vec<I>, rng: &mut dyn RngCore) -> RunReport<I>
+    where
+        I: Individual<G> + MaybeSendSync,
+    {
+        self.run_with(population, rng, |population| population)
+    }
+
+    // `evaluate` gets every new population before it's measured, so it can
+    // compute fitness externally (e.g. by running a simulation).
+
+    pub fn run_with<I>(
+        &mut self,
+        population: Vec<I>,
+        rng: &mut dyn RngCore,
+        mut evaluate: impl FnMut(Vec<I>) -> Vec<I>,
+    ) -> RunReport<I>
+    where
+        I: Individual<G> + MaybeSendSync,
+    {
+        let started = Instant::now();
+        let mut history = Vec::new();
+        let mut population = evaluate(population);
+
+        loop {
+            let (children, stats) = self.ga.iterate(&population, rng);
+
+            history.push(stats);
+
+            let elapsed = started.elapsed();
+
+            if let Some(condition) = self.termination.check(&history, elapsed) {
+                return RunReport {
+                    population,
+                    stopped_by: condition.clone(),
+                    history,
+                    elapsed,
+                };
+            }
+
+            population = evaluate(children);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::stats;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const NOW: Duration = Duration::from_secs(0);
+
+    #[test]
+    fn stagnation() {
+        let condition = Termination::Stagnation(2);
+
+        let history = vec![stats(1.0, 1.0), stats(3.0, 1.0), stats(2.0, 1.0)];
+        assert_eq!(condition.check(&history, NOW), None);
+
+        let history = vec![stats(1.0, 1.0), stats(3.0, 1.0), stats(2.0, 1.0), stats(3.0, 1.0)];
+        assert_eq!(condition.check(&history, NOW), Some(&condition));
+    }
+
+    #[test]
+    fn diversity_collapse() {
+        let condition = Termination::DiversityCollapse(0.5);
+
+        assert_eq!(condition.check(&[stats(1.0, 1.0)], NOW), None);
+        assert_eq!(condition.check(&[stats(1.0, 0.1)], NOW), Some(&condition));
+    }
+
+    #[test]
+    fn any_reports_the_condition_that_was_met() {
+        let condition = Termination::TargetFitness(100.0)
+            .or(Termination::WallClock(Duration::from_secs(5)));
+
+        assert_eq!(condition.check(&[stats(1.0, 1.0)], NOW), None);
+
+        assert_eq!(
+            condition.check(&[stats(1.0, 1.0)], Duration::from_secs(6)),
+            Some(&Termination::WallClock(Duration::from_secs(5))),
+        );
+    }
+
+    #[test]
+    fn all_needs_every_condition() {
+        let condition = Termination::MaxGenerations(2)
+            .and(Termination::TargetFitness(2.0));
+
+        assert_eq!(condition.check(&[stats(1.0, 1.0), stats(3.0, 1.0)], NOW), Some(&condition));
+        assert_eq!(condition.check(&[stats(1.0, 1.0), stats(1.0, 1.0)], NOW), None);
+        assert_eq!(condition.check(&[stats(3.0, 1.0)], NOW), None);
+    }
+
+    #[test]
+    fn run_stops_after_max_generations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut driver = Driver::new(
+            GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.5, 0.5),
+            ),
+            Termination::MaxGenerations(10).or(Termination::TargetFitness(1000.0)),
+        );
+
+        let population = (0..4)
+            .map(|idx| TestIndiv::create([1.0, idx as f32].into_iter().collect()))
+            .collect();
+
+        let report = driver.run(population, &mut rng);
+
+        assert_eq!(report.generations(), 10);
+        assert_eq!(report.stopped_by, Termination::MaxGenerations(10));
+        assert_eq!(report.population.len(), 4);
+    }
+}
@@ -0,0 +1,67 @@
+// Test helpers shared between modules
+
+use crate::*;
+
+// Lineage-tracking individuals, for the modules dealing with lineages
+
+#[derive(Clone, Debug)]
+pub struct Tracked {
+    pub chromosome: Chromosome,
+    pub lineage: Option<Lineage>,
+}
+
+impl Individual for Tracked {
+    // Never zero, so that roulette wheel selection always has a pick
+    fn fitness(&self) -> f32 {
+        self.chromosome.iter().sum::<f32>().max(0.1)
+    }
+
+    fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    fn create(chromosome: Chromosome) -> Self {
+        Self { chromosome, lineage: None }
+    }
+
+    fn lineage(&self) -> Option<&Lineage> {
+        self.lineage.as_ref()
+    }
+
+    fn set_lineage(&mut self, lineage: Lineage) {
+        self.lineage = Some(lineage);
+    }
+}
+
+// A founder for each of `ids`, with `genes(id)` for its chromosome
+
+pub fn founders<S, const N: usize>(
+    ga: &mut GeneticAlgorithm<S>,
+    ids: impl IntoIterator<Item = usize>,
+    genes: impl Fn(f32) -> [f32; N],
+) -> Vec<Tracked>
+where
+    S: SelectionMethod,
+{
+    let mut population: Vec<_> = ids
+        .into_iter()
+        .map(|id| Tracked::create(genes(id as f32).into_iter().collect()))
+        .collect();
+
+    ga.assign_lineage(&mut population);
+    population
+}
+
+// Statistics of a population where both individuals have a fitness of
+// `max_fit`, and each gene deviates from its mean by `diversity`
+
+pub fn stats(max_fit: f32, diversity: f32) -> Statistics {
+    let spread = diversity * 2.0;
+
+    let pop = vec![
+        TestIndiv::create([0.0, max_fit].into_iter().collect()),
+        TestIndiv::create([spread, max_fit - spread].into_iter().collect()),
+    ];
+
+    Statistics::new(&pop)
+}
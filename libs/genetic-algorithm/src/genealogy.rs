@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+// Where an individual comes from; assigned by `GeneticAlgorithm::iterate`
+// (or `GeneticAlgorithm::assign_lineage` for the initial population).
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Lineage {
+    pub id: u64,
+
+    // Empty for individuals of the initial population
+    pub parents: Vec<u64>,
+
+    pub generation: usize,
+
+    // Whether the chromosome was recombined from the parents' ones
+    pub crossover: bool,
+
+    // How many genes the mutation changed
+    pub mutations: usize,
+}
+
+impl Lineage {
+    pub fn founder(id: u64, generation: usize) -> Self {
+        Self {
+            id,
+            parents: Vec::new(),
+            generation,
+            crossover: false,
+            mutations: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GenealogyRecord {
+    pub lineage: Lineage,
+
+    // Known once the individual has been part of a population passed to
+    // `GeneticAlgorithm::iterate`
+    pub fitness: Option<f32>,
+}
+
+// Family tree of every individual a `GeneticAlgorithm` has created.
+// Serialized as its list of records; the index is rebuilt on load.
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<GenealogyRecord>", into = "Vec<GenealogyRecord>")]
+pub struct Genealogy {
+    records: Vec<GenealogyRecord>,
+    index: HashMap<u64, usize>,
+}
+
+impl Genealogy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record(&mut self, lineage: Lineage) {
+        self.index.insert(lineage.id, self.records.len());
+        self.records.push(GenealogyRecord { lineage, fitness: None });
+    }
+
+    pub(crate) fn record_fitness(&mut self, id: u64, fitness: f32) {
+        if let Some(&idx) = self.index.get(&id) {
+            self.records[idx].fitness = Some(fitness);
+        }
+    }
+
+    pub fn records(&self) -> &[GenealogyRecord] {
+        &self.records
+    }
+
+    pub fn get(&self, id: u64) -> Option<&GenealogyRecord> {
+        self.index.get(&id).map(|&idx| &self.records[idx])
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    // Every known ancestor of `id`, closest generations first
+
+    pub fn ancestors(&self, id: u64) -> Vec<&GenealogyRecord> {
+        let mut ancestors = Vec::new();
+        let mut queue: Vec<u64> = match self.get(id) {
+            Some(record) => record.lineage.parents.clone(),
+            None => return ancestors,
+        };
+
+        while !queue.is_empty() {
+            let mut next = Vec::new();
+
+            for id in queue {
+                if ancestors.iter().any(|record: &&GenealogyRecord| record.lineage.id == id) {
+                    continue;
+                }
+
+                if let Some(record) = self.get(id) {
+                    next.extend(record.lineage.parents.iter().copied());
+                    ancestors.push(record);
+                }
+            }
+
+            queue = next;
+        }
+
+        ancestors
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph genealogy {\n");
+
+        for record in &self.records {
+            let lineage = &record.lineage;
+
+            let _ = write!(dot, "    {} [label=\"#{} (gen {})", lineage.id, lineage.id, lineage.generation);
+
+            if let Some(fitness) = record.fitness {
+                let _ = write!(dot, "\\nfitness {:.2}", fitness);
+            }
+
+            dot.push_str("\"];\n");
+
+            for parent in &lineage.parents {
+                let _ = writeln!(dot, "    {} -> {};", parent, lineage.id);
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    // One row per individual; `parents` is a `;`-separated list of ids
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("id,generation,parents,crossover,mutations,fitness\n");
+
+        for record in &self.records {
+            let lineage = &record.lineage;
+
+            let parents: Vec<_> = lineage
+                .parents
+                .iter()
+                .map(|parent| parent.to_string())
+                .collect();
+
+            let fitness = record
+                .fitness
+                .map(|fitness| fitness.to_string())
+                .unwrap_or_default();
+
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                lineage.id,
+                lineage.generation,
+                parents.join(";"),
+                lineage.crossover,
+                lineage.mutations,
+                fitness,
+            );
+        }
+
+        csv
+    }
+}
+
+impl From<Vec<GenealogyRecord>> for Genealogy {
+    fn from(records: Vec<GenealogyRecord>) -> Self {
+        let index = records
+            .iter()
+            .enumerate()
+            .map(|(idx, record)| (record.lineage.id, idx))
+            .collect();
+
+        Self { records, index }
+    }
+}
+
+impl From<Genealogy> for Vec<GenealogyRecord> {
+    fn from(genealogy: Genealogy) -> Self {
+        genealogy.records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures::*, *};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn children_point_at_their_parents() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_genealogy();
+
+        let mut population = founders(&mut ga, 0..4, |idx| [idx, 1.0]);
+
+        for _ in 0..3 {
+            population = ga.iterate(&population, &mut rng).0;
+        }
+
+        let genealogy = ga.genealogy().unwrap();
+
+        assert_eq!(genealogy.len(), 16);
+
+        for indiv in &population {
+            let lineage = indiv.lineage().unwrap();
+
+            assert_eq!(lineage.generation, 3);
+            assert_eq!(lineage.parents.len(), 2);
+
+            for parent in &lineage.parents {
+                let parent = genealogy.get(*parent).unwrap();
+
+                assert_eq!(parent.lineage.generation, 2);
+                assert!(parent.fitness.is_some());
+            }
+
+            let founders = genealogy
+                .ancestors(lineage.id)
+                .into_iter()
+                .filter(|record| record.lineage.parents.is_empty())
+                .count();
+
+            assert!(founders > 0);
+        }
+
+        let csv = genealogy.to_csv();
+        assert_eq!(csv.lines().count(), 17);
+        assert!(csv.starts_with("id,generation,parents,crossover,mutations,fitness\n0,0,,false,0,"));
+
+        let dot = genealogy.to_dot();
+        assert!(dot.starts_with("digraph genealogy {\n"));
+        assert_eq!(dot.matches(" -> ").count(), 24);
+    }
+
+    #[test]
+    fn bred_children_are_recorded() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_genealogy();
+
+        let population = founders(&mut ga, 0..2, |idx| [idx, 1.0]);
+
+        let child = ga.breed(&[&population[0], &population[1]], &mut rng);
+        let grandchild = ga.breed(&[&child], &mut rng);
+
+        let lineage = child.lineage().unwrap();
+        assert_eq!(lineage.id, 2);
+        assert_eq!(lineage.generation, 1);
+        assert_eq!(lineage.parents, vec![0, 1]);
+
+        let lineage = grandchild.lineage().unwrap();
+        assert_eq!(lineage.id, 3);
+        assert_eq!(lineage.generation, 2);
+        assert_eq!(lineage.parents, vec![2]);
+        assert!(!lineage.crossover);
+
+        let genealogy = ga.genealogy().unwrap();
+        assert_eq!(genealogy.len(), 4);
+        assert!(genealogy.get(0).unwrap().fitness.is_some());
+        assert_eq!(genealogy.ancestors(3).len(), 3);
+
+        // Breeding on its own doesn't advance the generation
+        assert_eq!(ga.generation(), 0);
+    }
+}
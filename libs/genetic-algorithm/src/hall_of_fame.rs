@@ -0,0 +1,136 @@
+use crate::*;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HallOfFameEntry<G = f32> {
+    pub chromosome: Chromosome<G>,
+    pub fitness: f32,
+    pub generation: usize,
+}
+
+// Keeps the best `capacity` distinct individuals seen across all
+// generations, best first.
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HallOfFame<G = f32> {
+    capacity: usize,
+    entries: Vec<HallOfFameEntry<G>>,
+}
+
+impl<G> HallOfFame<G>
+where
+    G: Clone + PartialEq,
+{
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+
+        Self {
+            capacity,
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn update<I>(&mut self, population: &[I], generation: usize)
+    where
+        I: Individual<G>,
+    {
+        for indiv in population {
+            self.insert(indiv.chromosome(), indiv.fitness(), generation);
+        }
+    }
+
+    fn insert(&mut self, chromosome: &Chromosome<G>, fitness: f32, generation: usize) {
+        if self.entries.len() == self.capacity
+            && self.entries.last().is_some_and(|worst| worst.fitness >= fitness)
+        {
+            return;
+        }
+
+        // The same chromosome only counts once, with its best fitness
+
+        if let Some(idx) = self
+            .entries
+            .iter()
+            .position(|entry| entry.chromosome.iter().eq(chromosome.iter()))
+        {
+            if self.entries[idx].fitness >= fitness {
+                return;
+            }
+
+            self.entries.remove(idx);
+        }
+
+        let idx = self
+            .entries
+            .partition_point(|entry| entry.fitness >= fitness);
+
+        self.entries.insert(idx, HallOfFameEntry {
+            chromosome: chromosome.clone(),
+            fitness,
+            generation,
+        });
+
+        self.entries.truncate(self.capacity);
+    }
+
+    pub fn best(&self) -> Option<&HallOfFameEntry<G>> {
+        self.entries.first()
+    }
+
+    pub fn entries(&self) -> &[HallOfFameEntry<G>] {
+        &self.entries
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn individual(genes: &[f32]) -> TestIndiv {
+        TestIndiv::create(genes.iter().cloned().collect())
+    }
+
+    #[test]
+    fn keeps_the_best_distinct_individuals() {
+        let mut hof = HallOfFame::new(2);
+
+        hof.update(&[individual(&[1.0]), individual(&[3.0])], 0);
+        hof.update(&[individual(&[3.0]), individual(&[2.0]), individual(&[0.5])], 1);
+
+        let entries: Vec<_> = hof
+            .entries()
+            .iter()
+            .map(|entry| (entry.fitness, entry.generation))
+            .collect();
+
+        assert_eq!(entries, vec![(3.0, 0), (2.0, 1)]);
+    }
+
+    #[test]
+    fn best_survives_worse_generations() {
+        let mut hof = HallOfFame::new(3);
+
+        hof.update(&[individual(&[5.0, 5.0])], 0);
+
+        for generation in 1..10 {
+            hof.update(&[individual(&[1.0, generation as f32 / 10.0])], generation);
+        }
+
+        let best = hof.best().unwrap();
+
+        assert_eq!(best.chromosome, individual(&[5.0, 5.0]).chromosome().clone());
+        assert_eq!(best.generation, 0);
+        assert_eq!(hof.len(), 3);
+    }
+}
@@ -0,0 +1,31 @@
+use crate::*;
+
+pub trait Individual<G = f32> {
+    fn fitness(&self) -> f32;
+    fn chromosome(&self) -> &Chromosome<G>;
+    fn create(chromosome: Chromosome<G>) -> Self;
+
+    // How much the individual violates its constraints; subtracted from
+    // its fitness when it's selected for reproduction, so infeasible
+    // individuals get fewer children without being ruled out entirely.
+
+    fn penalty(&self) -> f32 {
+        0.0
+    }
+
+    // What selection goes by; statistics, the hall of fame and genealogies
+    // keep reporting the raw fitness.
+
+    fn adjusted_fitness(&self) -> f32 {
+        (self.fitness() - self.penalty()).max(0.0)
+    }
+
+    // Individuals that want to be tracked in a `Genealogy` store the
+    // lineage `GeneticAlgorithm` gives them and hand it back here.
+
+    fn lineage(&self) -> Option<&Lineage> {
+        None
+    }
+
+    fn set_lineage(&mut self, _lineage: Lineage) {}
+}
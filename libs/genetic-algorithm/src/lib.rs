@@ -1,272 +1,471 @@
-#![feature(type_alias_impl_trait)]
+pub use self::{
+    bounds::*, checkpoint::*, chromosome::*, coevolution::*, crossover::*, driver::*,
+    genealogy::*, hall_of_fame::*, individual::*, mutation::*, observer::*, rates::*,
+    selection::*, statistics::*,
+};
+mod bounds;
+mod checkpoint;
+mod chromosome;
+mod coevolution;
+mod crossover;
+mod driver;
+mod genealogy;
+mod hall_of_fame;
+mod individual;
+mod mutation;
+mod observer;
+mod rates;
+mod selection;
+mod statistics;
+
+#[cfg(test)]
+mod fixtures;
+
 use std::ops::Index;
+use std::time::{Duration, Instant};
 
 use rand::{Rng, RngCore, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "parallel")]
+use rand::SeedableRng;
+#[cfg(feature = "parallel")]
+use rand_chacha::ChaCha8Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Bounds `GeneticAlgorithm::iterate` needs from the selection method and
+// the individuals: `Send + Sync` with the `parallel` feature, nothing
+// otherwise.
+
+#[cfg(feature = "parallel")]
+pub trait MaybeSendSync: Send + Sync {}
+
+#[cfg(feature = "parallel")]
+impl<T> MaybeSendSync for T where T: Send + Sync {}
 
-pub struct GeneticAlgorithm <S> {
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSendSync {}
+
+#[cfg(not(feature = "parallel"))]
+impl<T> MaybeSendSync for T {}
+
+pub struct GeneticAlgorithm <S, G = f32> {
     selection_method: S,
-    crossover_method: Box<dyn CrossoverMethod>,
-    mutation_method: Box<dyn MutationMethod>,
+    crossover_method: Box<dyn CrossoverMethod<G> + Send + Sync>,
+    mutation_method: Box<dyn MutationMethod<G> + Send + Sync>,
+    generation: usize,
+    next_id: u64,
+    genealogy: Option<Genealogy>,
+    observers: Vec<Box<dyn Observer<G> + Send + Sync>>,
+    crossover_rate: f32,
+    reproduction: Reproduction,
+    rate_controller: Option<Box<dyn RateController + Send + Sync>>,
 }
 
-impl<S> GeneticAlgorithm<S> 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Reproduction {
+    // Two parents, recombined with probability `crossover_rate` - otherwise
+    // the child is a clone of the first one
+    Sexual,
+
+    // A single parent per child, cloned and then mutated; `crossover_rate`
+    // is ignored
+    Asexual,
+}
+
+// A child as it comes out of `GeneticAlgorithm::spawn_child`, along with
+// what observers get to see of its creation.
+
+struct Offspring<'a, I, G> {
+    child: I,
+    lineage: Lineage,
+    parents: Vec<&'a I>,
+    crossed: Chromosome<G>,
+}
+
+impl<S, G> GeneticAlgorithm<S, G> 
 where 
     S: SelectionMethod,
 {
     pub fn new(
         selection_method: S,
-        crossover_method: impl CrossoverMethod + 'static,
-        mutation_method: impl MutationMethod + 'static,
+        crossover_method: impl CrossoverMethod<G> + Send + Sync + 'static,
+        mutation_method: impl MutationMethod<G> + Send + Sync + 'static,
     ) -> Self {
         Self { selection_method,
                 crossover_method: Box::new(crossover_method),
                 mutation_method: Box::new(mutation_method),
+                generation: 0,
+                next_id: 0,
+                genealogy: None,
+                observers: Vec::new(),
+                crossover_rate: 1.0,
+                reproduction: Reproduction::Sexual,
+                rate_controller: None,
             }
     }
 
-    pub fn iterate<I>(&self, 
-        population: &[I],
-        rng: &mut dyn RngCore,
-    )-> (Vec<I>, Statistics)
-    where
-        I: Individual,
-    {
-        
-        // pre condition checking
+    pub fn with_crossover_rate(mut self, crossover_rate: f32) -> Self {
+        assert!((0.0..=1.0).contains(&crossover_rate));
 
-        assert!(!population.is_empty());
+        self.crossover_rate = crossover_rate;
+        self
+    }
 
-        let new_pop = (0..population.len())
-            .map(|_| {
-                let parent_a = self
-                    .selection_method
-                    .select(population, rng);
-                let parent_b = self
-                    .selection_method
-                    .select(population, rng);
+    pub fn with_reproduction(mut self, reproduction: Reproduction) -> Self {
+        self.reproduction = reproduction;
+        self
+    }
 
-                let chromosome_a = parent_a.chromosome();
-                let chromosome_b = parent_b.chromosome();
+    pub fn reproduction(&self) -> Reproduction {
+        self.reproduction
+    }
 
-                let mut child = self
-                     .crossover_method
-                     .crossover(rng, chromosome_a, chromosome_b);
+    // Lets `controller` adjust the crossover rate and the mutation
+    // parameters after every generation
 
-                self.mutation_method.mutate(rng, &mut child);
-                I::create(child)
-            })
-          .collect();
+    pub fn with_rate_controller(
+        mut self,
+        controller: impl RateController + Send + Sync + 'static,
+    ) -> Self {
+        self.rate_controller = Some(Box::new(controller));
+        self
+    }
 
-          let stats = Statistics::new(population);
+    pub fn rates(&self) -> OperatorRates {
+        OperatorRates {
+            crossover_rate: self.crossover_rate,
+            mutation: self.mutation_method.params(),
+        }
+    }
 
-          (new_pop, stats)
+    pub fn set_rates(&mut self, rates: OperatorRates) {
+        assert!((0.0..=1.0).contains(&rates.crossover_rate));
+
+        self.crossover_rate = rates.crossover_rate;
+
+        if let Some(params) = rates.mutation {
+            self.mutation_method.set_params(params);
+        }
     }
-}
 
-#[derive(Clone, Debug)]
-pub struct Chromosome {
-    genes: Vec<f32>,
-}
+    pub fn with_observer(mut self, observer: impl Observer<G> + Send + Sync + 'static) -> Self {
+        self.add_observer(observer);
+        self
+    }
 
-impl Chromosome {
-    pub fn len(&self) -> usize {
-        self.genes.len()
+    pub fn add_observer(&mut self, observer: impl Observer<G> + Send + Sync + 'static) {
+        self.observers.push(Box::new(observer));
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &f32> {
-        self.genes.iter()
+    // Records the lineage of every individual created from now on
+
+    pub fn with_genealogy(mut self) -> Self {
+        self.genealogy = Some(Genealogy::new());
+        self
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
-        self.genes.iter_mut()
+    pub fn genealogy(&self) -> Option<&Genealogy> {
+        self.genealogy.as_ref()
     }
-}
 
-impl Index<usize> for Chromosome {
-    type Output = f32;
+    // Number of generations created so far
 
-    fn index(&self, index:usize) -> &Self::Output {
-        &self.genes[index]
+    pub fn generation(&self) -> usize {
+        self.generation
     }
-}
 
-impl FromIterator<f32> for Chromosome {
-    fn from_iter<T: IntoIterator<Item = f32>> (iter: T) -> Self {
-        Self { 
-            genes: iter.into_iter().collect(),
+    // Gives individuals without a lineage (e.g. a randomly created initial
+    // population) one, so that their children can refer to them.
+
+    pub fn assign_lineage<I>(&mut self, population: &mut [I])
+    where
+        I: Individual<G>,
+    {
+        for indiv in population {
+            if indiv.lineage().is_some() {
+                continue;
+            }
+
+            let lineage = Lineage::founder(self.next_id, self.generation);
+            self.next_id += 1;
+
+            if let Some(genealogy) = &mut self.genealogy {
+                genealogy.record(lineage.clone());
+            }
+
+            indiv.set_lineage(lineage);
         }
     }
-}
 
-impl IntoIterator for Chromosome {
-    type Item = f32;
-    type IntoIter = impl Iterator<Item = f32>;
+    // Creates a single child outside of `iterate`, for simulations where
+    // individuals reproduce whenever they're ready rather than all at once.
+    // Two parents are recombined with probability `crossover_rate` (unless
+    // reproduction is asexual), a single one is cloned; either way the
+    // child is then mutated. Its generation is one past its parents'.
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.genes.into_iter()
-    }
-}
+    pub fn breed<I>(&mut self, parents: &[&I], rng: &mut dyn RngCore) -> I
+    where
+        I: Individual<G>,
+        G: Gene,
+    {
+        assert!(matches!(parents.len(), 1 | 2));
 
-pub trait CrossoverMethod {
-     fn crossover(
-        &self,
-        rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-     ) -> Chromosome;
-}
+        let crossover = parents.len() == 2 && match self.reproduction {
+            Reproduction::Sexual => rng.gen_bool(self.crossover_rate as _),
+            Reproduction::Asexual => false,
+        };
+
+        let parents = if crossover { parents.to_vec() } else { vec![parents[0]] };
+
+        let generation = parents
+            .iter()
+            .filter_map(|parent| parent.lineage())
+            .map(|lineage| lineage.generation + 1)
+            .max()
+            .unwrap_or(self.generation);
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let Offspring { child, lineage, parents, crossed } = self.offspring(parents, rng, id, generation);
+
+        if let Some(genealogy) = &mut self.genealogy {
+            for parent in &parents {
+                if let Some(lineage) = parent.lineage() {
+                    genealogy.record_fitness(lineage.id, parent.fitness());
+                }
+            }
+
+            genealogy.record(lineage.clone());
+        }
 
-#[derive(Clone, Debug)]
-pub struct UniformCrossover;
+        let parents: Vec<_> = parents
+            .iter()
+            .map(|parent| parent.chromosome())
+            .collect();
 
-impl UniformCrossover {
-    pub fn new() -> Self {
-        Self
+        for observer in &mut self.observers {
+            observer.child_created(&parents, &crossed);
+            observer.child_mutated(child.chromosome(), &lineage);
+        }
+
+        child
     }
-}
-impl CrossoverMethod for UniformCrossover {
-    fn crossover(
-        &self,
+
+    pub fn iterate<I>(&mut self, 
+        population: &[I],
         rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-     ) -> Chromosome {
+    )-> (Vec<I>, Statistics)
+    where
+        S: MaybeSendSync,
+        I: Individual<G> + MaybeSendSync,
+        G: Gene,
+    {
+        
+        // pre condition checking
+
+        assert!(!population.is_empty());
 
-        assert_eq!(parent_a.len(), parent_b.len()); //precondition
+        let first_id = self.next_id;
 
-        let parent_a_iter = parent_a.iter();
-        let parent_b_iter = parent_b.iter();
+        self.next_id += population.len() as u64;
+        self.generation += 1;
 
-        const RATIO:f64 = 0.5; 
-        parent_a_iter
-            .zip(parent_b_iter)
-            .map(|(&a, &b)| if rng.gen_bool(RATIO) {a} else {b} )
-            .collect()
+        if !self.observers.is_empty() {
+            let chromosomes: Vec<_> = population
+                .iter()
+                .map(|indiv| indiv.chromosome())
+                .collect();
+
+            let fitness: Vec<_> = population
+                .iter()
+                .map(|indiv| indiv.fitness())
+                .collect();
+
+            for observer in &mut self.observers {
+                observer.before_selection(self.generation, &chromosomes, &fitness);
+            }
         }
-}
 
-pub trait MutationMethod {
-    fn mutate(
-        &self,
-        rng: &mut dyn RngCore,
-        child: &mut Chromosome
-    );
-}
-#[derive(Clone, Debug)]
-pub struct GaussianMutation{
-    chance: f32, //probability of one gene mutating
+        let offspring = self.spawn_children(population, rng, first_id);
 
-    coeff: f32,
-}
+        if let Some(genealogy) = &mut self.genealogy {
+            for indiv in population {
+                if let Some(lineage) = indiv.lineage() {
+                    genealogy.record_fitness(lineage.id, indiv.fitness());
+                }
+            }
+        }
 
-impl GaussianMutation {
-    pub fn new(chance:f32, coeff:f32) -> Self {
-        assert!(chance>= 0.0 && chance <= 1.0);
+        let mut new_pop = Vec::with_capacity(offspring.len());
 
-        Self { chance, coeff }
-    }
-}
+        for Offspring { child, lineage, parents, crossed } in offspring {
+            let parents: Vec<_> = parents
+                .iter()
+                .map(|parent| parent.chromosome())
+                .collect();
 
-impl MutationMethod for GaussianMutation {
-    fn mutate(
-        &self,
-        rng: &mut dyn RngCore,
-        child: &mut Chromosome
-    ) {
-        const NEGATIVE: f32 = -1.0;
-        const POSITIVE: f32 = 1.0;
-        const HALF: f64 = 0.5;
-        child
-        .iter_mut()
-        .for_each(|gene| {
-            let sign = if rng.gen_bool(HALF) {NEGATIVE} else {POSITIVE};
-            if rng.gen_bool(self.chance as _) {
-                *gene += sign * self.coeff * rng.gen::<f32>();
+            for observer in &mut self.observers {
+                observer.child_created(&parents, &crossed);
+
+                observer.child_mutated(child.chromosome(), &lineage);
             }
-        })
-    }
-}
-pub trait Individual {
-    fn fitness(&self) -> f32;
-    fn chromosome(&self) -> &Chromosome;
-    fn create(chromosome: Chromosome) -> Self;
-}
-pub trait SelectionMethod {
 
-    fn select<'a,I>(
-        &self, 
-        population: &'a [I],
-        rng: &mut dyn RngCore) -> &'a I
-    where 
-        I: Individual;
-}
+            if let Some(genealogy) = &mut self.genealogy {
+                genealogy.record(lineage);
+            }
+
+            new_pop.push(child);
+        }
+
+        let stats = Statistics::new(population).with_rates(self.rates());
+
+        let mut rates = self.rates();
 
-pub struct RouletteWheelSelection;
+        if let Some(controller) = &mut self.rate_controller {
+            controller.adapt(&mut rates, &stats);
+            self.set_rates(rates);
+        }
+
+        for observer in &mut self.observers {
+            observer.generation_completed(self.generation, &stats);
+        }
 
-impl RouletteWheelSelection {
-    pub fn new() -> Self {
-        Self
+        (new_pop, stats)
     }
-}
 
-impl SelectionMethod for RouletteWheelSelection {
-    fn select<'a,I>(
-        &self, 
+    #[cfg(not(feature = "parallel"))]
+    fn spawn_children<'a, I>(
+        &self,
         population: &'a [I],
-        rng: &mut dyn RngCore) -> &'a I
-    where 
-        I: Individual,
+        rng: &mut dyn RngCore,
+        first_id: u64,
+    ) -> Vec<Offspring<'a, I, G>>
+    where
+        I: Individual<G>,
+        G: Gene,
     {
-        population
-            .choose_weighted(rng, |indiv| indiv.fitness())
-            .expect("Empty population")
+        (0..population.len())
+            .map(|idx| self.spawn_child(population, rng, first_id + idx as u64))
+            .collect()
     }
-}
 
-#[derive(Clone, Debug)]
-pub struct Statistics {
-    min_fit: f32,
-    max_fit: f32,
-    avg_fit: f32,
-}
-impl Statistics {
-    fn new<I>(pop: &[I]) -> Self 
+    // Each child gets its own rng, seeded from a single master seed drawn
+    // from `rng` plus the child's index, so the outcome doesn't depend on
+    // how rayon splits the work between threads.
+
+    #[cfg(feature = "parallel")]
+    fn spawn_children<'a, I>(
+        &self,
+        population: &'a [I],
+        rng: &mut dyn RngCore,
+        first_id: u64,
+    ) -> Vec<Offspring<'a, I, G>>
     where
-        I:Individual,
+        S: Sync,
+        I: Individual<G> + Send + Sync,
+        G: Gene,
     {
-        assert!(!pop.is_empty());
+        let seed: u64 = rng.gen();
 
-        let mut min_fit = pop[0].fitness();
-        let mut max_fit = min_fit;
-        let mut sum_fit = 0.0;
+        (0..population.len())
+            .into_par_iter()
+            .map(|idx| {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                rng.set_stream(idx as u64);
 
-        for indiv in pop {
-            let fitness = indiv.fitness();
-
-            min_fit = min_fit.min(fitness);
-            max_fit = max_fit.max(fitness);
-            sum_fit += fitness;
-        }
-
-        Self {
-            min_fit,
-            max_fit,
-            avg_fit: sum_fit / (pop.len() as f32),
-        }
+                self.spawn_child(population, &mut rng, first_id + idx as u64)
+            })
+            .collect()
     }
 
-    pub fn min_fitness(&self) -> f32 {
-        self.min_fit
+    fn spawn_child<'a, I>(
+        &self,
+        population: &'a [I],
+        rng: &mut dyn RngCore,
+        id: u64,
+    ) -> Offspring<'a, I, G>
+    where
+        I: Individual<G>,
+        G: Gene,
+    {
+        let parent_a = self
+            .selection_method
+            .select(population, rng);
+
+        let crossover = match self.reproduction {
+            Reproduction::Sexual => rng.gen_bool(self.crossover_rate as _),
+            Reproduction::Asexual => false,
+        };
+
+        let parents = if crossover {
+            let parent_b = self
+                .selection_method
+                .select(population, rng);
+
+            vec![parent_a, parent_b]
+        } else {
+            vec![parent_a]
+        };
+
+        self.offspring(parents, rng, id, self.generation)
     }
 
-    pub fn max_fitness(&self) -> f32 {
-        self.max_fit
-    }
+    // Recombines the parents if there are two of them (clones the only one
+    // otherwise) and mutates the result
 
-    pub fn avg_fitness(&self) -> f32 {
-        self.avg_fit
+    fn offspring<'a, I>(
+        &self,
+        parents: Vec<&'a I>,
+        rng: &mut dyn RngCore,
+        id: u64,
+        generation: usize,
+    ) -> Offspring<'a, I, G>
+    where
+        I: Individual<G>,
+        G: Gene,
+    {
+        let mut child = match parents.as_slice() {
+            [parent_a, parent_b] => self
+                .crossover_method
+                .crossover(rng, parent_a.chromosome(), parent_b.chromosome()),
+            _ => parents[0].chromosome().clone(),
+        };
+
+        let crossed = child.clone();
+
+        self.mutation_method.mutate(rng, &mut child);
+
+        let lineage = Lineage {
+            id,
+            parents: parents
+                .iter()
+                .filter_map(|parent| parent.lineage())
+                .map(|lineage| lineage.id)
+                .collect(),
+            generation,
+            crossover: parents.len() > 1,
+            mutations: crossed
+                .iter()
+                .zip(child.iter())
+                .filter(|(before, after)| before != after)
+                .count(),
+        };
+
+        let mut child = I::create(child);
+        child.set_lineage(lineage.clone());
+
+        Offspring {
+            child,
+            lineage,
+            parents,
+            crossed,
+        }
     }
 }
+
 // Testing Part
 //
 //
@@ -290,18 +489,6 @@ pub enum TestIndiv {
 
     WithFitness { fitness: f32}, 
 }
-#[cfg(test)]
-
- impl PartialEq for Chromosome {
-
-    fn eq(&self, other: &Self) -> bool {
-        approx::relative_eq!(
-             self.genes.as_slice(),
-             other.genes.as_slice(),
-        )
-    }
-}
-
 #[cfg(test)]
 impl TestIndiv {
      pub fn new(fitness: f32) -> Self {
@@ -382,6 +569,184 @@ pub mod tests {
 
             assert_eq!(actual_histogram, expected_histogram);
     }
+
+    #[test]
+    fn selection_goes_by_penalized_fitness() {
+        // Fitness is the first gene, penalty the second one
+        struct Constrained(Chromosome);
+
+        impl Individual for Constrained {
+            fn fitness(&self) -> f32 {
+                self.0[0]
+            }
+
+            fn chromosome(&self) -> &Chromosome {
+                &self.0
+            }
+
+            fn create(chromosome: Chromosome) -> Self {
+                Self(chromosome)
+            }
+
+            fn penalty(&self) -> f32 {
+                self.0[1]
+            }
+        }
+
+        let population: Vec<_> = [[5.0, 5.0], [1.0, 0.0], [9.0, 10.0]]
+            .into_iter()
+            .map(|genes| Constrained::create(genes.into_iter().collect()))
+            .collect();
+
+        let method = RouletteWheelSelection::new();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        for _ in 0..100 {
+            assert_eq!(method.select(&population, &mut rng).fitness(), 1.0);
+        }
+    }
+
+    #[test]
+    fn iterate_with_binary_genes() {
+        struct Bits(Chromosome<bool>);
+
+        impl Individual<bool> for Bits {
+            fn fitness(&self) -> f32 {
+                1.0 + self.0.iter().filter(|bit| **bit).count() as f32
+            }
+
+            fn chromosome(&self) -> &Chromosome<bool> {
+                &self.0
+            }
+
+            fn create(chromosome: Chromosome<bool>) -> Self {
+                Self(chromosome)
+            }
+        }
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            SinglePointCrossover::new(),
+            BitFlipMutation::new(0.05),
+        );
+
+        let mut population: Vec<_> = (0..20)
+            .map(|_| Bits::create((0..16).map(|_| rng.gen_bool(0.5)).collect()))
+            .collect();
+
+        let first = ga.iterate(&population, &mut rng).1;
+
+        for _ in 0..30 {
+            population = ga.iterate(&population, &mut rng).0;
+        }
+
+        let last = ga.iterate(&population, &mut rng).1;
+
+        assert!(last.avg_fitness() > first.avg_fitness());
+    }
+
+    fn clone_counts(ga: GeneticAlgorithm<RouletteWheelSelection>) -> (usize, usize) {
+        let mut ga = ga;
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = fixtures::founders(&mut ga, 1..=10, |idx| [idx, 10.0 + idx, 20.0 + idx]);
+
+        let children = ga.iterate(&population, &mut rng).0;
+
+        let clones = children
+            .iter()
+            .filter(|child| {
+                population
+                    .iter()
+                    .any(|parent| parent.chromosome == child.chromosome)
+            })
+            .count();
+
+        let single_parent = children
+            .iter()
+            .filter(|child| !child.lineage.as_ref().unwrap().crossover)
+            .filter(|child| child.lineage.as_ref().unwrap().parents.len() == 1)
+            .count();
+
+        (clones, single_parent)
+    }
+
+    #[test]
+    fn crossover_rate() {
+        let ga = |crossover_rate| {
+            GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.0, 0.0),
+            )
+            .with_crossover_rate(crossover_rate)
+        };
+
+        assert_eq!(clone_counts(ga(0.0)), (10, 10));
+        assert_eq!(clone_counts(ga(1.0)).1, 0);
+
+        // Without mutation, every single-parent child is a clone
+        let (clones, single_parent) = clone_counts(ga(0.5));
+
+        assert!(single_parent > 0 && single_parent < 10);
+        assert!(clones >= single_parent);
+    }
+
+    #[test]
+    fn asexual_reproduction_ignores_crossover_rate() {
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_crossover_rate(1.0)
+        .with_reproduction(Reproduction::Asexual);
+
+        assert_eq!(clone_counts(ga), (10, 10));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_iterate_ignores_thread_count() {
+        fn individual(genes: &[f32]) -> TestIndiv {
+            TestIndiv::create(genes.iter().cloned().collect())
+        }
+
+        let population = vec![
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 1.0]),
+            individual(&[1.0, 2.0, 1.0]),
+            individual(&[1.0, 2.0, 4.0]),
+        ];
+
+        let evolve = |threads: usize| {
+            let mut ga = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.5, 0.5),
+            );
+
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut population = population.clone();
+
+            pool.install(|| {
+                for _ in 0..10 {
+                    population = ga.iterate(&population, &mut rng).0;
+                }
+            });
+
+            population
+        };
+
+        assert_eq!(evolve(1), evolve(4));
+    }
 }
 
 #[cfg(test)]
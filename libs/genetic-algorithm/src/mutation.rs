@@ -0,0 +1,240 @@
+use crate::*;
+use rand::distributions::uniform::SampleUniform;
+
+pub trait MutationMethod<G = f32> {
+    fn mutate(
+        &self,
+        rng: &mut dyn RngCore,
+        child: &mut Chromosome<G>
+    );
+
+    // Tunable parameters, so a `RateController` can adjust them; `None`
+    // for methods that don't have any.
+
+    fn params(&self) -> Option<MutationParams> {
+        None
+    }
+
+    fn set_params(&mut self, _params: MutationParams) {}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MutationParams {
+    // Probability of one gene mutating
+    pub chance: f32,
+
+    // Size of the change; ignored by methods that don't have one
+    pub coeff: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct GaussianMutation{
+    chance: f32, //probability of one gene mutating
+
+    coeff: f32,
+}
+
+impl GaussianMutation {
+    pub fn new(chance:f32, coeff:f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance, coeff }
+    }
+}
+
+impl MutationMethod for GaussianMutation {
+    fn mutate(
+        &self,
+        rng: &mut dyn RngCore,
+        child: &mut Chromosome
+    ) {
+        const NEGATIVE: f32 = -1.0;
+        const POSITIVE: f32 = 1.0;
+        const HALF: f64 = 0.5;
+        child
+        .iter_mut()
+        .for_each(|gene| {
+            let sign = if rng.gen_bool(HALF) {NEGATIVE} else {POSITIVE};
+            if rng.gen_bool(self.chance as _) {
+                *gene += sign * self.coeff * rng.gen::<f32>();
+            }
+        })
+    }
+
+    fn params(&self) -> Option<MutationParams> {
+        Some(MutationParams { chance: self.chance, coeff: self.coeff })
+    }
+
+    fn set_params(&mut self, params: MutationParams) {
+        self.chance = params.chance.clamp(0.0, 1.0);
+        self.coeff = params.coeff;
+    }
+}
+
+// Binary genes: flips each bit with probability `chance`.
+
+#[derive(Clone, Debug)]
+pub struct BitFlipMutation {
+    chance: f32,
+}
+
+impl BitFlipMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl MutationMethod<bool> for BitFlipMutation {
+    fn mutate(
+        &self,
+        rng: &mut dyn RngCore,
+        child: &mut Chromosome<bool>
+    ) {
+        child
+        .iter_mut()
+        .for_each(|gene| {
+            if rng.gen_bool(self.chance as _) {
+                *gene = !*gene;
+            }
+        })
+    }
+
+    fn params(&self) -> Option<MutationParams> {
+        Some(MutationParams { chance: self.chance, coeff: 0.0 })
+    }
+
+    fn set_params(&mut self, params: MutationParams) {
+        self.chance = params.chance.clamp(0.0, 1.0);
+    }
+}
+
+// Integer genes: replaces each gene with probability `chance` by a value
+// drawn uniformly from `min..=max`. Enums can be evolved this way by
+// encoding them as their variant index.
+
+#[derive(Clone, Debug)]
+pub struct RandomResetMutation<T> {
+    chance: f32,
+    min: T,
+    max: T,
+}
+
+impl<T> RandomResetMutation<T>
+where
+    T: PartialOrd,
+{
+    pub fn new(chance: f32, min: T, max: T) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(min <= max);
+
+        Self { chance, min, max }
+    }
+}
+
+impl<T> MutationMethod<T> for RandomResetMutation<T>
+where
+    T: SampleUniform + PartialOrd + Copy,
+{
+    fn mutate(
+        &self,
+        rng: &mut dyn RngCore,
+        child: &mut Chromosome<T>
+    ) {
+        child
+        .iter_mut()
+        .for_each(|gene| {
+            if rng.gen_bool(self.chance as _) {
+                *gene = rng.gen_range(self.min..=self.max);
+            }
+        })
+    }
+
+    fn params(&self) -> Option<MutationParams> {
+        Some(MutationParams { chance: self.chance, coeff: 0.0 })
+    }
+
+    fn set_params(&mut self, params: MutationParams) {
+        self.chance = params.chance.clamp(0.0, 1.0);
+    }
+}
+
+// Permutation genes: swaps each position with probability `chance` with
+// another random position, so the chromosome stays a permutation.
+
+#[derive(Clone, Debug)]
+pub struct SwapMutation {
+    chance: f32,
+}
+
+impl SwapMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl<G> MutationMethod<G> for SwapMutation {
+    fn mutate(
+        &self,
+        rng: &mut dyn RngCore,
+        child: &mut Chromosome<G>
+    ) {
+        for idx in 0..child.len() {
+            if rng.gen_bool(self.chance as _) {
+                let other = rng.gen_range(0..child.len());
+                child.swap(idx, other);
+            }
+        }
+    }
+
+    fn params(&self) -> Option<MutationParams> {
+        Some(MutationParams { chance: self.chance, coeff: 0.0 })
+    }
+
+    fn set_params(&mut self, params: MutationParams) {
+        self.chance = params.chance.clamp(0.0, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn bit_flip_with_full_chance_negates_every_gene() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child: Chromosome<bool> = vec![true, false, true].into_iter().collect();
+
+        BitFlipMutation::new(1.0).mutate(&mut rng, &mut child);
+
+        assert_eq!(child.into_iter().collect::<Vec<_>>(), vec![false, true, false]);
+    }
+
+    #[test]
+    fn random_reset_stays_within_range() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child: Chromosome<i32> = vec![0; 100].into_iter().collect();
+
+        RandomResetMutation::new(1.0, 3, 5).mutate(&mut rng, &mut child);
+
+        assert!(child.iter().all(|gene| (3..=5).contains(gene)));
+    }
+
+    #[test]
+    fn swap_keeps_a_permutation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child: Chromosome<usize> = (0..10).collect();
+
+        SwapMutation::new(0.5).mutate(&mut rng, &mut child);
+
+        let mut genes: Vec<_> = child.into_iter().collect();
+        genes.sort_unstable();
+
+        assert_eq!(genes, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,91 @@
+use crate::*;
+
+// Hooks into `GeneticAlgorithm::iterate`, for logging, metrics or custom
+// analysis; every callback does nothing by default.
+//
+// Callbacks are invoked on the calling thread in a fixed order, even when
+// children are created in parallel: `before_selection`, then
+// `child_created` / `child_mutated` for each child, then
+// `generation_completed`.
+
+pub trait Observer<G = f32> {
+    // `population` and `fitness` are in the same order; `generation` is the
+    // one about to be created
+    fn before_selection(
+        &mut self,
+        _generation: usize,
+        _population: &[&Chromosome<G>],
+        _fitness: &[f32],
+    ) {
+    }
+
+    // `child` is the result of the crossover (or a clone of the only
+    // parent), before mutation
+    fn child_created(&mut self, _parents: &[&Chromosome<G>], _child: &Chromosome<G>) {}
+
+    fn child_mutated(&mut self, _child: &Chromosome<G>, _lineage: &Lineage) {}
+
+    // `stats` describe the population the generation was created from
+    fn generation_completed(&mut self, _generation: usize, _stats: &Statistics) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Log(Arc<Mutex<Vec<String>>>);
+
+    impl Observer for Log {
+        fn before_selection(&mut self, generation: usize, population: &[&Chromosome], fitness: &[f32]) {
+            assert_eq!(population.len(), fitness.len());
+
+            self.0.lock().unwrap().push(format!("select {}", generation));
+        }
+
+        fn child_created(&mut self, parents: &[&Chromosome], child: &Chromosome) {
+            assert_eq!(parents[0].len(), child.len());
+
+            self.0.lock().unwrap().push("create".into());
+        }
+
+        fn child_mutated(&mut self, _child: &Chromosome, lineage: &Lineage) {
+            self.0.lock().unwrap().push(format!("mutate {}", lineage.id));
+        }
+
+        fn generation_completed(&mut self, generation: usize, _stats: &Statistics) {
+            self.0.lock().unwrap().push(format!("done {}", generation));
+        }
+    }
+
+    #[test]
+    fn observers_see_every_step() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let log = Log::default();
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_observer(log.clone());
+
+        let population: Vec<_> = (1..=2)
+            .map(|idx| TestIndiv::create([idx as f32, 1.0].into_iter().collect()))
+            .collect();
+
+        let population = ga.iterate(&population, &mut rng).0;
+        ga.iterate(&population, &mut rng);
+
+        assert_eq!(
+            *log.0.lock().unwrap(),
+            vec![
+                "select 1", "create", "mutate 0", "create", "mutate 1", "done 1",
+                "select 2", "create", "mutate 2", "create", "mutate 3", "done 2",
+            ],
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use crate::*;
+
+// Operator parameters a `GeneticAlgorithm` is currently running with
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OperatorRates {
+    // Probability of a child being recombined from two parents rather than
+    // cloned from one
+    pub crossover_rate: f32,
+
+    // `None` if the mutation method has no tunable parameters
+    pub mutation: Option<MutationParams>,
+}
+
+// Adjusts operator rates as evolution goes on; `adapt` is called after each
+// generation with the statistics of the population it was created from.
+
+pub trait RateController {
+    fn adapt(&mut self, rates: &mut OperatorRates, stats: &Statistics);
+
+    // What the controller has learned from past generations, so that a
+    // `Checkpoint` can pick up where it left off; controllers going by the
+    // latest statistics alone have nothing to save.
+
+    fn state(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    fn restore_state(&mut self, _state: serde_json::Value) -> serde_json::Result<()> {
+        Ok(())
+    }
+}
+
+// Rechenberg's 1/5th success rule: every `window` generations, if more than
+// a fifth of them improved on the best fitness so far the mutation
+// coefficient is multiplied by `factor` (bigger steps), if fewer it's
+// divided by it.
+
+#[derive(Clone, Debug)]
+pub struct OneFifthRule {
+    window: usize,
+    factor: f32,
+    history: History,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct History {
+    best: Option<f32>,
+
+    // Generations of the current window so far, and how many improved
+    trials: usize,
+    successes: usize,
+}
+
+impl OneFifthRule {
+    pub fn new(window: usize, factor: f32) -> Self {
+        assert!(window > 0);
+        assert!(factor > 1.0);
+
+        Self {
+            window,
+            factor,
+            history: History::default(),
+        }
+    }
+}
+
+impl RateController for OneFifthRule {
+    fn adapt(&mut self, rates: &mut OperatorRates, stats: &Statistics) {
+        const TARGET: f32 = 1.0 / 5.0;
+
+        let fitness = stats.max_fitness();
+        let history = &mut self.history;
+
+        match history.best {
+            Some(best) if fitness <= best => {}
+            Some(_) => {
+                history.best = Some(fitness);
+                history.successes += 1;
+            }
+            None => {
+                // The first generation sets the baseline and doesn't count
+                history.best = Some(fitness);
+                return;
+            }
+        }
+
+        history.trials += 1;
+
+        if history.trials < self.window {
+            return;
+        }
+
+        let ratio = history.successes as f32 / history.trials as f32;
+
+        history.trials = 0;
+        history.successes = 0;
+
+        if let Some(mutation) = &mut rates.mutation {
+            if ratio > TARGET {
+                mutation.coeff *= self.factor;
+            } else if ratio < TARGET {
+                mutation.coeff /= self.factor;
+            }
+        }
+    }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(&self.history).ok()
+    }
+
+    fn restore_state(&mut self, state: serde_json::Value) -> serde_json::Result<()> {
+        self.history = serde_json::from_value(state)?;
+        Ok(())
+    }
+}
+
+// Keeps population diversity (see `Statistics::diversity`) around `target`:
+// below it, mutation chance goes up and crossover rate down by `factor`
+// (recombining near-identical parents achieves little), above it the
+// opposite.
+
+#[derive(Clone, Debug)]
+pub struct DiversityControl {
+    target: f32,
+    factor: f32,
+}
+
+impl DiversityControl {
+    pub fn new(target: f32, factor: f32) -> Self {
+        assert!(target >= 0.0);
+        assert!(factor > 1.0);
+
+        Self { target, factor }
+    }
+}
+
+impl RateController for DiversityControl {
+    fn adapt(&mut self, rates: &mut OperatorRates, stats: &Statistics) {
+        let diversity = stats.diversity();
+
+        let scale = if diversity < self.target {
+            self.factor
+        } else if diversity > self.target {
+            1.0 / self.factor
+        } else {
+            return;
+        };
+
+        if let Some(mutation) = &mut rates.mutation {
+            mutation.chance = (mutation.chance * scale).clamp(0.0, 1.0);
+        }
+
+        rates.crossover_rate = (rates.crossover_rate / scale).clamp(0.0, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::stats;
+
+    fn rates() -> OperatorRates {
+        OperatorRates {
+            crossover_rate: 0.5,
+            mutation: Some(MutationParams { chance: 0.1, coeff: 0.2 }),
+        }
+    }
+
+    #[test]
+    fn one_fifth_rule() {
+        let mut rule = OneFifthRule::new(2, 2.0);
+        let mut rates = rates();
+
+        // baseline, then two improvements in a row
+        for fitness in [1.0, 2.0, 3.0] {
+            rule.adapt(&mut rates, &stats(fitness, 1.0));
+        }
+
+        approx::assert_relative_eq!(rates.mutation.unwrap().coeff, 0.4);
+
+        // two generations without improvement
+        for fitness in [3.0, 1.0] {
+            rule.adapt(&mut rates, &stats(fitness, 1.0));
+        }
+
+        approx::assert_relative_eq!(rates.mutation.unwrap().coeff, 0.2);
+        approx::assert_relative_eq!(rates.crossover_rate, 0.5);
+    }
+
+    #[test]
+    fn one_fifth_rule_resumes_its_window() {
+        let mut rule = OneFifthRule::new(2, 2.0);
+        let mut rates = rates();
+
+        for fitness in [1.0, 2.0] {
+            rule.adapt(&mut rates, &stats(fitness, 1.0));
+        }
+
+        let mut resumed = OneFifthRule::new(2, 2.0);
+        resumed.restore_state(rule.state().unwrap()).unwrap();
+
+        // Completes the window started before the state was saved
+        resumed.adapt(&mut rates, &stats(3.0, 1.0));
+
+        approx::assert_relative_eq!(rates.mutation.unwrap().coeff, 0.4);
+    }
+
+    #[test]
+    fn diversity_control() {
+        let mut control = DiversityControl::new(0.5, 2.0);
+        let mut rates = rates();
+
+        control.adapt(&mut rates, &stats(1.0, 0.1));
+
+        approx::assert_relative_eq!(rates.mutation.unwrap().chance, 0.2);
+        approx::assert_relative_eq!(rates.crossover_rate, 0.25);
+
+        control.adapt(&mut rates, &stats(1.0, 1.0));
+        control.adapt(&mut rates, &stats(1.0, 1.0));
+
+        approx::assert_relative_eq!(rates.mutation.unwrap().chance, 0.05);
+        approx::assert_relative_eq!(rates.crossover_rate, 1.0);
+    }
+
+    #[test]
+    fn statistics_report_the_rates_in_use() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.1, 0.5),
+        )
+        .with_rate_controller(DiversityControl::new(100.0, 2.0));
+
+        let population: Vec<_> = (1..=4)
+            .map(|idx| TestIndiv::create([idx as f32, 1.0].into_iter().collect()))
+            .collect();
+
+        let (population, first) = ga.iterate(&population, &mut rng);
+        let (_, second) = ga.iterate(&population, &mut rng);
+
+        assert_eq!(first.rates().unwrap().mutation.unwrap().chance, 0.1);
+        assert_eq!(second.rates().unwrap().mutation.unwrap().chance, 0.2);
+        assert_eq!(ga.rates().mutation.unwrap().chance, 0.4);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::*;
+use rand::distributions::WeightedError;
+
+pub trait SelectionMethod {
+
+    fn select<'a, I, G>(
+        &self, 
+        population: &'a [I],
+        rng: &mut dyn RngCore) -> &'a I
+    where 
+        I: Individual<G>;
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RouletteWheelSelection;
+
+impl RouletteWheelSelection {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SelectionMethod for RouletteWheelSelection {
+    fn select<'a, I, G>(
+        &self, 
+        population: &'a [I],
+        rng: &mut dyn RngCore) -> &'a I
+    where 
+        I: Individual<G>,
+    {
+        match population.choose_weighted(rng, |indiv| indiv.adjusted_fitness()) {
+            Ok(indiv) => indiv,
+
+            // Nobody did better than anybody else (e.g. no animal found any
+            // food), so everybody gets the same chance
+            Err(WeightedError::AllWeightsZero) => population
+                .choose(rng)
+                .expect("Empty population"),
+
+            Err(WeightedError::NoItem) => panic!("Empty population"),
+            Err(err) => panic!("Invalid fitness: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn zero_fitness_everywhere_is_a_uniform_pick() {
+        let population = vec![TestIndiv::new(0.0); 4];
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let picked: Vec<_> = (0..100)
+            .map(|_| {
+                let indiv = RouletteWheelSelection::new().select(&population, &mut rng);
+
+                population.iter().position(|other| std::ptr::eq(other, indiv)).unwrap()
+            })
+            .collect();
+
+        for idx in 0..population.len() {
+            assert!(picked.contains(&idx));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Empty population")]
+    fn empty_population_is_reported() {
+        let population: Vec<TestIndiv> = Vec::new();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        RouletteWheelSelection::new().select(&population, &mut rng);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::*;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Statistics {
+    min_fit: f32,
+    max_fit: f32,
+    avg_fit: f32,
+    diversity: f32,
+    rates: Option<OperatorRates>,
+}
+impl Statistics {
+    pub fn new<I, G>(pop: &[I]) -> Self 
+    where
+        I: Individual<G>,
+        G: Gene,
+    {
+        assert!(!pop.is_empty());
+
+        let mut min_fit = pop[0].fitness();
+        let mut max_fit = min_fit;
+        let mut sum_fit = 0.0;
+
+        for indiv in pop {
+            let fitness = indiv.fitness();
+
+            min_fit = min_fit.min(fitness);
+            max_fit = max_fit.max(fitness);
+            sum_fit += fitness;
+        }
+
+        Self {
+            min_fit,
+            max_fit,
+            avg_fit: sum_fit / (pop.len() as f32),
+            diversity: Self::diversity_of(pop),
+            rates: None,
+        }
+    }
+
+    // Standard deviation of every gene across the population, averaged over
+    // all genes; drops to zero once every individual is the same.
+
+    fn diversity_of<I, G>(pop: &[I]) -> f32
+    where
+        I: Individual<G>,
+        G: Gene,
+    {
+        let genes = pop[0].chromosome().len();
+
+        if genes == 0 {
+            return 0.0;
+        }
+
+        let len = pop.len() as f32;
+        let mut sums = vec![0.0; genes];
+        let mut squares = vec![0.0; genes];
+
+        for indiv in pop {
+            for (idx, gene) in indiv.chromosome().iter().enumerate() {
+                let value = gene.value();
+
+                sums[idx] += value;
+                squares[idx] += value * value;
+            }
+        }
+
+        let deviations: f32 = sums
+            .iter()
+            .zip(&squares)
+            .map(|(sum, square)| {
+                let mean = sum / len;
+
+                (square / len - mean * mean).max(0.0).sqrt()
+            })
+            .sum();
+
+        deviations / genes as f32
+    }
+
+    pub fn min_fitness(&self) -> f32 {
+        self.min_fit
+    }
+
+    pub fn max_fitness(&self) -> f32 {
+        self.max_fit
+    }
+
+    pub fn avg_fitness(&self) -> f32 {
+        self.avg_fit
+    }
+
+    pub fn diversity(&self) -> f32 {
+        self.diversity
+    }
+
+    // Operator rates the next generation was created with; set for
+    // statistics returned by `GeneticAlgorithm::iterate`
+
+    pub fn rates(&self) -> Option<&OperatorRates> {
+        self.rates.as_ref()
+    }
+
+    pub fn with_rates(mut self, rates: OperatorRates) -> Self {
+        self.rates = Some(rates);
+        self
+    }
+}
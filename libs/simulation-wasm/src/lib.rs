@@ -4,7 +4,6 @@ use rand::prelude::*;
 use serde::Serialize;
 #[wasm_bindgen]
 pub struct Simulation {
-    rng: ThreadRng,
     sim: sim::Simulation,
 }
 
@@ -12,27 +11,38 @@ pub struct Simulation {
 impl Simulation {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        let mut rng = thread_rng();
-        let sim = sim::Simulation::random(&mut rng);
+        let sim = sim::Simulation::random(&mut thread_rng());
 
-        Self { rng, sim }
+        Self { sim }
+    }
+
+    // Replays the run that reported this seed
+    #[wasm_bindgen(js_name = fromSeed)]
+    pub fn from_seed(seed: u64) -> Self {
+        Self { sim: sim::Simulation::from_seed(seed) }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.sim.seed()
     }
 
     pub fn world (&self) -> JsValue {
-        let world = World::from(self.sim.world());
+        let world = World::from(&self.sim);
 
         JsValue::from_serde(&world).unwrap()
     }
 
     pub fn step(&mut self) {
-        self.sim.step(&mut self.rng);
+        self.sim.step();
     }
 
     pub fn train(&mut self) -> String {
-        let stats = self.sim.train(&mut self.rng);
+        let stats = self.sim.train();
 
         format!(
-            "min={:.2}, max={:.2}, avg={:.2}",
+            "seed={}, generation={}, min={:.2}, max={:.2}, avg={:.2}",
+            self.sim.seed(),
+            self.sim.generation(),
             stats.min_fitness(),
             stats.max_fitness(),
             stats.avg_fitness()
@@ -49,19 +59,29 @@ pub struct Animal {
 
 #[derive(Clone, Debug, Serialize)]
 pub struct World {
+    // A string, since JS numbers can't hold every u64
+    pub seed: String,
     pub animals: Vec<Animal>,
     pub foods: Vec<Food>,
+    pub predators: Vec<Animal>,
+
+    // Serialized as they are in the config, e.g. `{ shape: "circle", ... }`
+    pub obstacles: Vec<sim::Obstacle>,
 }
 
 #[derive(Clone, Debug, Serialize)]
 pub struct Food {
     pub x: f32,
     pub y: f32,
+
+    // Index into the config's food kinds
+    pub kind: usize,
 }
 
-impl From<&sim::World> for World {
+impl From<&sim::Simulation> for World {
 
-    fn from(world: &sim::World) -> Self {
+    fn from(sim: &sim::Simulation) -> Self {
+        let world = sim.world();
 
         let animals = world
             .animals()
@@ -72,10 +92,19 @@ impl From<&sim::World> for World {
         let foods = world
             .foods()
             .iter()
+            .filter(|food| food.is_available())
             .map(Food::from)
             .collect();
 
-        Self { animals, foods }
+        let predators = world
+            .predators()
+            .iter()
+            .map(Animal::from)
+            .collect();
+
+        let obstacles = sim.config().world.obstacles.clone();
+
+        Self { seed: sim.seed().to_string(), animals, foods, predators, obstacles }
     }
 }
 
@@ -95,6 +124,7 @@ impl From<&sim::Food> for Food {
         Self {
             x: food.position().x,
             y: food.position().y,
+            kind: food.kind(),
         }
     }
 }
\ No newline at end of file
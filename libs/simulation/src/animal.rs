@@ -3,36 +3,75 @@ use na::Point2;
 use lib_genetic_algorithm as ga;
 
 const INIT_SPEED:f32 = 0.002;
+
+// Animals eat food, predators eat animals; each species has its own
+// settings and genetic algorithm
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Species {
+    Prey,
+    Predator,
+}
+
+impl Species {
+    pub(crate) fn animal(self, config: &SimulationConfig) -> &AnimalConfig {
+        match self {
+            Self::Prey => &config.animal,
+            Self::Predator => &config.predators.animal,
+        }
+    }
+
+    pub(crate) fn eye(self, config: &SimulationConfig) -> &EyeConfig {
+        match self {
+            Self::Prey => &config.eye,
+            Self::Predator => &config.predators.eye,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Animal {
+    pub(crate) species: Species,
     pub(crate) position: Point2<f32>,
     pub(crate) rotation: na::Rotation2<f32>,
     pub(crate) speed: f32,
     pub(crate) eye : Eye,
     pub(crate) brain: Brain,
     pub(crate) satiation: usize,
-
+    pub(crate) energy: f32,
+    pub(crate) metrics: Metrics,
+    pub(crate) lineage: Option<ga::Lineage>,
 }
 
 impl Animal {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-
-
-        let eye = Eye::default();
+    pub fn random(config: &SimulationConfig, species: Species, rng: &mut dyn RngCore) -> Self {
+        let eye = Eye::new(species.eye(config));
 
         let brain = Brain::random(rng, &eye);
 
-        Self::new(eye, brain, rng)
+        Self::new(config, species, eye, brain, None, rng)
     }
 
-    fn new(eye: Eye, brain: Brain, rng: &mut dyn RngCore) -> Self {
+    fn new(
+        config: &SimulationConfig,
+        species: Species,
+        eye: Eye,
+        brain: Brain,
+        lineage: Option<ga::Lineage>,
+        rng: &mut dyn RngCore,
+    ) -> Self {
         Self {
-            position: rng.gen(),
+            species,
+            position: Obstacle::free_position(&config.world, rng)
+                .expect("obstacles are validated to leave room"),
             rotation: rng.gen(),
             speed:INIT_SPEED,
             eye,
             brain,
             satiation: 0,
+            energy: config.energy.initial,
+            metrics: Metrics::default(),
+            lineage,
         }
     }
 
@@ -41,14 +80,44 @@ impl Animal {
     }
 
     pub(crate) fn from_chromosome(
+        config: &SimulationConfig,
+        species: Species,
         chromosome: ga::Chromosome,
+        lineage: Option<ga::Lineage>,
         rng: &mut dyn RngCore
     ) -> Self {
-        let eye = Eye::default();
+        let eye = Eye::new(species.eye(config));
 
         let brain =  Brain::from_chromosome(chromosome, &eye);
 
-        Self::new(eye, brain, rng)
+        Self::new(config, species, eye, brain, lineage, rng)
+    }
+
+    // Lets the brain adjust speed and rotation based on what the animal sees
+
+    pub(crate) fn steer(&mut self, vision: Vec<f32>, config: &SimulationConfig) {
+        let config = self.species.animal(config);
+        let response = self.brain.nn.propagate(vision);
+
+        let rel_speed = response[0].clamp(
+            -config.speed_accel,
+            config.speed_accel,
+        );
+
+        let rel_rotation = response[1].clamp(
+            -config.rotation_accel,
+            config.rotation_accel,
+        );
+
+        self.speed = (self.speed + rel_speed).clamp(config.speed_min, config.speed_max);
+
+        self.rotation = na::Rotation2::new(
+            self.rotation.angle() + rel_rotation,
+        );
+    }
+
+    pub fn species(&self) -> Species {
+        self.species
     }
     pub fn position(&self) -> Point2<f32> {
         self.position
@@ -56,4 +125,23 @@ impl Animal {
     pub fn rotation(&self) -> na::Rotation2<f32> {
         self.rotation
     }
+
+    // Food eaten, less what predators took and toxic food cost
+    pub fn satiation(&self) -> usize {
+        self.satiation
+    }
+
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    // Only drained and replenished in `Mode::OpenEnded`
+    pub fn energy(&self) -> f32 {
+        self.energy
+    }
+
+    // Set once the animal has been through `Simulation::evolve`
+    pub fn lineage(&self) -> Option<&ga::Lineage> {
+        self.lineage.as_ref()
+    }
 }
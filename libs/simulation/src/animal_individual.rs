@@ -3,11 +3,12 @@ use crate::*;
 pub struct AnimalIndividual{
     fitness: f32,
     chromosome: ga::Chromosome,
+    lineage: Option<ga::Lineage>,
 }
 
 impl ga::Individual for AnimalIndividual {
     fn create(chromosome: ga::Chromosome) -> Self {
-        Self { fitness: 0.0, chromosome }
+        Self { fitness: 0.0, chromosome, lineage: None }
     }
 
     fn chromosome(&self) -> &ga::Chromosome {
@@ -17,17 +18,26 @@ impl ga::Individual for AnimalIndividual {
     fn fitness(&self) -> f32 {
         self.fitness
     }
+
+    fn lineage(&self) -> Option<&ga::Lineage> {
+        self.lineage.as_ref()
+    }
+
+    fn set_lineage(&mut self, lineage: ga::Lineage) {
+        self.lineage = Some(lineage);
+    }
 }
 
 impl AnimalIndividual {
-    pub fn from_animal(animal: &Animal) -> Self {
+    pub fn from_animal(animal: &Animal, fitness: &dyn Fitness) -> Self {
         Self { 
-            fitness: animal.satiation as f32, 
+            fitness: fitness.fitness(animal).max(0.0), 
             chromosome: animal.as_chromosome(),
+            lineage: animal.lineage.clone(),
         }
     }
 
-    pub fn into_animal(self, rng: &mut dyn RngCore) -> Animal {
-        Animal::from_chromosome(self.chromosome, rng)
+    pub fn into_animal(self, config: &SimulationConfig, species: Species, rng: &mut dyn RngCore) -> Animal {
+        Animal::from_chromosome(config, species, self.chromosome, self.lineage, rng)
     }
 }
\ No newline at end of file
@@ -0,0 +1,214 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+// What happens at the edges of the world:
+//
+// - `wrap` - animals leaving on one side come back on the other, so the
+//   world is a torus; animals also see, reach and run into obstacles
+//   across the edges,
+//
+// - `bounce` - edges are walls animals bounce off of,
+//
+// - `stop` - edges are walls animals stop at, until they turn away,
+//
+// - `lethal` - animals leaving the world die; in `Mode::Generational` (and
+//   always for predators) that means losing everything they've eaten and
+//   respawning somewhere at random.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Boundary {
+    #[default]
+    Wrap,
+    Bounce,
+    Stop,
+    Lethal,
+}
+
+impl Boundary {
+    // Shortest way from `from` to `to`
+
+    pub fn offset(self, from: na::Point2<f32>, to: na::Point2<f32>) -> na::Vector2<f32> {
+        let offset = to - from;
+
+        match self {
+            Self::Wrap => offset.map(|axis| axis - axis.round()),
+            _ => offset,
+        }
+    }
+
+    pub fn distance(self, a: na::Point2<f32>, b: na::Point2<f32>) -> f32 {
+        self.offset(a, b).norm()
+    }
+
+    // `obstacles` as animals run into them and see them: across a wrapping
+    // edge, each of them shows up on the other side as well
+
+    pub(crate) fn repeat(self, obstacles: &[Obstacle]) -> Vec<Obstacle> {
+        match self {
+            Self::Wrap => {
+                let shifts = [0.0, -1.0, 1.0];
+
+                shifts
+                    .iter()
+                    .flat_map(|&x| shifts.iter().map(move |&y| na::Vector2::new(x, y)))
+                    .flat_map(|by| obstacles.iter().map(move |obstacle| obstacle.shifted(by)))
+                    .collect()
+            }
+            _ => obstacles.to_vec(),
+        }
+    }
+
+    // Moves `animal` by `step`; returns whether it's still in the world
+    // afterwards
+
+    pub(crate) fn advance(self, animal: &mut Animal, step: na::Vector2<f32>) -> bool {
+        let mut position = animal.position + step;
+
+        match self {
+            Self::Wrap => {
+                position.x = na::wrap(position.x, 0.0, 1.0);
+                position.y = na::wrap(position.y, 0.0, 1.0);
+            }
+
+            Self::Bounce => {
+                let mut heading = step;
+
+                for axis in 0..2 {
+                    if position[axis] < 0.0 {
+                        position[axis] = -position[axis];
+                        heading[axis] = -heading[axis];
+                    } else if position[axis] > 1.0 {
+                        position[axis] = 2.0 - position[axis];
+                        heading[axis] = -heading[axis];
+                    }
+                }
+
+                // Animals head towards `rotation * (0, 1)`
+                if heading != step {
+                    animal.rotation = na::Rotation2::new((-heading.x).atan2(heading.y));
+                }
+            }
+
+            Self::Stop => {
+                position.x = position.x.clamp(0.0, 1.0);
+                position.y = position.y.clamp(0.0, 1.0);
+            }
+
+            Self::Lethal => {
+                if !(0.0..=1.0).contains(&position.x) || !(0.0..=1.0).contains(&position.y) {
+                    return false;
+                }
+            }
+        }
+
+        animal.position = position;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn animal(x: f32, y: f32, angle: f32) -> Animal {
+        let mut animal = Animal::random(&SimulationConfig::default(), Species::Prey, &mut ChaCha8Rng::seed_from_u64(0));
+
+        animal.position = na::Point2::new(x, y);
+        animal.rotation = na::Rotation2::new(angle);
+        animal
+    }
+
+    fn step(animal: &Animal, length: f32) -> na::Vector2<f32> {
+        animal.rotation * na::Vector2::new(0.0, length)
+    }
+
+    #[test]
+    fn only_wrap_measures_across_edges() {
+        let (a, b) = (na::Point2::new(0.05, 0.5), na::Point2::new(0.95, 0.5));
+
+        assert!((Boundary::Wrap.distance(a, b) - 0.1).abs() < 1e-5);
+        assert!((Boundary::Wrap.offset(a, b).x + 0.1).abs() < 1e-5);
+        assert!((Boundary::Bounce.distance(a, b) - 0.9).abs() < 1e-5);
+    }
+
+    #[test]
+    fn edges_behave_as_configured() {
+        // Heading up, 0.05 away from the top edge
+        let moved = |boundary: Boundary| {
+            let mut animal = animal(0.5, 0.95, 0.0);
+            let step = step(&animal, 0.1);
+            let alive = boundary.advance(&mut animal, step);
+
+            (animal, alive)
+        };
+
+        let (animal, _) = moved(Boundary::Wrap);
+        assert!((animal.position.y - 0.05).abs() < 1e-5);
+
+        let (animal, _) = moved(Boundary::Bounce);
+        assert!((animal.position.y - 0.95).abs() < 1e-5);
+        assert!(step(&animal, 1.0).y < 0.0);
+
+        let (animal, _) = moved(Boundary::Stop);
+        assert_eq!(animal.position.y, 1.0);
+
+        let (animal, alive) = moved(Boundary::Lethal);
+        assert!(!alive);
+        assert_eq!(animal.position.y, 0.95);
+    }
+
+    #[test]
+    fn obstacles_wrap_around_too() {
+        let walls = vec![Obstacle::Segment {
+            from: na::Point2::new(0.005, 0.0),
+            to: na::Point2::new(0.005, 1.0),
+        }];
+
+        // Heading right, 0.005 away from the right edge
+        let config = |boundary| SimulationConfig {
+            world: WorldConfig {
+                obstacles: walls.clone(),
+                boundary,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let moved = |boundary| {
+            let mut simulation = Simulation::with_config(0, config(boundary)).unwrap();
+
+            let animal = &mut simulation.world.animals[0];
+            animal.position = na::Point2::new(0.995, 0.5);
+            animal.rotation = na::Rotation2::new(-FRAC_PI_2);
+            animal.speed = 0.02;
+
+            simulation.process_movement();
+            simulation.world.animals[0].position
+        };
+
+        assert_eq!(moved(Boundary::Wrap), na::Point2::new(0.995, 0.5));
+        assert!((moved(Boundary::Stop).x - 1.0).abs() < 1e-5);
+
+        let eye = Eye::new(&EyeConfig {
+            channels: vec![Channel::Obstacles],
+            ..Default::default()
+        });
+
+        let sees = |boundary: Boundary| {
+            eye.perceive(
+                na::Point2::new(0.995, 0.5),
+                na::Rotation2::new(-FRAC_PI_2),
+                Species::Prey,
+                [],
+                &boundary.repeat(&walls),
+            )
+            .iter()
+            .any(|&cell| cell > 0.0)
+        };
+
+        assert!(sees(Boundary::Wrap));
+        assert!(!sees(Boundary::Bounce));
+    }
+}
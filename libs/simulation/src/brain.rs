@@ -12,6 +12,10 @@ impl Brain {
         }
     }
 
+    pub fn weights(&self) -> impl Iterator<Item = f32> + '_ {
+        self.nn.weights()
+    }
+
     pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
         self.nn.weights().collect()
     }
@@ -28,10 +32,19 @@ impl Brain {
         }
     }
 
+    // Number of genes a chromosome needs to encode a brain for `eye`
+
+    pub(crate) fn chromosome_len(eye: &Eye) -> usize {
+        Self::topology(eye)
+            .windows(2)
+            .map(|layers| (layers[0].neurons + 1) * layers[1].neurons)
+            .sum()
+    }
+
     fn topology(eye: &Eye) -> [nn::LayerTopology; 3] {
         [
             nn::LayerTopology {
-                neurons: eye.cells(),
+                neurons: eye.inputs(),
             },
             nn::LayerTopology {
                 neurons: 2 * eye.cells(),
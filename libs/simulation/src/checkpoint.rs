@@ -0,0 +1,286 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+
+// What the GA doesn't know about an animal - brains are kept in the GA
+// checkpoint's population, in the same order.
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct AnimalState {
+    position: na::Point2<f32>,
+    rotation: na::Rotation2<f32>,
+    speed: f32,
+    energy: f32,
+    satiation: usize,
+    metrics: Metrics,
+}
+
+// The predators' own population, see `PredatorConfig`
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct PredatorsCheckpoint {
+    ga: ga::Checkpoint,
+    animals: Vec<AnimalState>,
+    history: Vec<ga::Statistics>,
+    catches: Vec<(usize, usize)>,
+}
+
+// A `Simulation` frozen mid-generation, rng state included; resuming from
+// it replays exactly what the original would have done.
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimulationCheckpoint {
+    seed: u64,
+    ga: ga::Checkpoint,
+    config: SimulationConfig,
+    animals: Vec<AnimalState>,
+    foods: Vec<Food>,
+    patches: Vec<na::Point2<f32>>,
+    age: usize,
+    steps: usize,
+    hall_of_fame: ga::HallOfFame,
+    history: Vec<ga::Statistics>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    predators: Option<PredatorsCheckpoint>,
+}
+
+impl SimulationCheckpoint {
+    // Seed the checkpointed simulation was started from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn generation(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let checkpoint: Self = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+
+        checkpoint
+            .config
+            .validate()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        Ok(checkpoint)
+    }
+}
+
+impl AnimalState {
+    fn of(animals: &[Animal]) -> Vec<Self> {
+        animals
+            .iter()
+            .map(|animal| Self {
+                position: animal.position,
+                rotation: animal.rotation,
+                speed: animal.speed,
+                energy: animal.energy,
+                satiation: animal.satiation,
+                metrics: animal.metrics.clone(),
+            })
+            .collect()
+    }
+
+    fn restore(
+        states: Vec<Self>,
+        population: &[ga::CheckpointEntry],
+        config: &SimulationConfig,
+        species: Species,
+    ) -> io::Result<Vec<Animal>> {
+        if states.len() != population.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} animal states for {} brains", states.len(), population.len()),
+            ));
+        }
+
+        let animals = population
+            .iter()
+            .zip(states)
+            .map(|(entry, state)| {
+                let eye = Eye::new(species.eye(config));
+                let brain = Brain::from_chromosome(entry.chromosome.clone(), &eye);
+
+                Animal {
+                    species,
+                    position: state.position,
+                    rotation: state.rotation,
+                    speed: state.speed,
+                    eye,
+                    brain,
+                    satiation: state.satiation,
+                    energy: state.energy,
+                    metrics: state.metrics,
+                    lineage: entry.lineage.clone(),
+                }
+            })
+            .collect();
+
+        Ok(animals)
+    }
+}
+
+impl Simulation {
+    pub fn checkpoint(&self) -> SimulationCheckpoint {
+        let fitness = self.fitness();
+
+        let animals: Vec<_> = self
+            .world
+            .animals
+            .iter()
+            .map(|animal| AnimalIndividual::from_animal(animal, fitness))
+            .collect();
+
+        let predators = (self.config.predators.count > 0).then(|| PredatorsCheckpoint {
+            ga: ga::Checkpoint::new(self.coevolution.ga(PREDATORS), &self.predator_individuals(), &self.rng),
+            animals: AnimalState::of(&self.world.predators),
+            history: self.predator_history.clone(),
+            catches: self.catches.clone(),
+        });
+
+        SimulationCheckpoint {
+            seed: self.seed,
+            ga: ga::Checkpoint::new(self.coevolution.ga(PREY), &animals, &self.rng),
+            config: self.config.clone(),
+            animals: AnimalState::of(&self.world.animals),
+            foods: self.world.foods.clone(),
+            patches: self.world.patches.clone(),
+            age: self.age,
+            steps: self.steps,
+            hall_of_fame: self.hall_of_fame.clone(),
+            history: self.history.clone(),
+            predators,
+        }
+    }
+
+    // A fitness set through `with_fitness` can't be saved, so the resumed
+    // simulation goes by `config.fitness` until it's given one again
+
+    pub fn resume(checkpoint: SimulationCheckpoint) -> io::Result<Self> {
+        let config = checkpoint.config;
+
+        let mut coevolution = Self::coevolution(&config);
+        checkpoint.ga.restore::<_, AnimalIndividual>(coevolution.ga_mut(PREY))?;
+
+        let animals = AnimalState::restore(checkpoint.animals, &checkpoint.ga.population, &config, Species::Prey)?;
+
+        let (predators, predator_history, catches) = match checkpoint.predators {
+            Some(predators) => {
+                predators.ga.restore::<_, AnimalIndividual>(coevolution.ga_mut(PREDATORS))?;
+
+                (
+                    AnimalState::restore(predators.animals, &predators.ga.population, &config, Species::Predator)?,
+                    predators.history,
+                    predators.catches,
+                )
+            }
+            None => (Vec::new(), Vec::new(), Vec::new()),
+        };
+
+        Ok(Self {
+            seed: checkpoint.seed,
+            rng: checkpoint.ga.rng,
+            world: World { animals, foods: checkpoint.foods, predators, patches: checkpoint.patches },
+            coevolution,
+            config,
+            age: checkpoint.age,
+            steps: checkpoint.steps,
+            hall_of_fame: checkpoint.hall_of_fame,
+            history: checkpoint.history,
+            predator_history,
+            catches,
+            fitness: None,
+        })
+    }
+
+    pub fn save_checkpoint(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.checkpoint().save(path)
+    }
+
+    pub fn load_checkpoint(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::resume(SimulationCheckpoint::load(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn fingerprint(simulation: &Simulation) -> String {
+        serde_json::to_string(&simulation.checkpoint()).unwrap()
+    }
+
+    #[test_case(RateControl::Fixed ; "fixed rates")]
+    #[test_case(RateControl::OneFifth { window: 2, factor: 2.0 } ; "one_fifth")]
+    fn resumes_bit_identically(rate_control: RateControl) {
+        let config = SimulationConfig {
+            evolution: EvolutionConfig {
+                generation_length: 30,
+                rate_control,
+                ..Default::default()
+            },
+            predators: PredatorConfig { count: 3, ..Default::default() },
+            eye: EyeConfig {
+                channels: vec![Channel::Food, Channel::Predators],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut simulation = Simulation::with_config(0, config).unwrap();
+
+        // A few generations in, so rate controllers have a history
+        for _ in 0..3 {
+            simulation.train();
+        }
+
+        for _ in 0..10 {
+            simulation.step();
+        }
+
+        let path = std::env::temp_dir().join(format!("checkpoint-{}.json", std::process::id()));
+        simulation.save_checkpoint(&path).unwrap();
+
+        let mut resumed = Simulation::load_checkpoint(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(resumed.seed(), 0);
+        assert_eq!(fingerprint(&resumed), fingerprint(&simulation));
+
+        // Crosses several generation boundaries, so evolution is covered too
+        for _ in 0..5 {
+            simulation.train();
+            resumed.train();
+        }
+
+        for _ in 0..10 {
+            simulation.step();
+            resumed.step();
+        }
+
+        assert_eq!(resumed.generation(), 8);
+        assert_eq!(resumed.predator_history().len(), 8);
+        assert_eq!(fingerprint(&resumed), fingerprint(&simulation));
+    }
+
+    #[test]
+    fn animals_must_match_their_brains() {
+        let mut checkpoint = Simulation::from_seed(0).checkpoint();
+        checkpoint.animals.pop();
+
+        let err = Simulation::resume(checkpoint).err().unwrap();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "9 animal states for 10 brains");
+    }
+}
@@ -0,0 +1,972 @@
+use crate::{Boundary, Channel, FoodSpawn, Obstacle, VisionModel};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+use std::path::Path;
+use std::{error, fmt, fs, io};
+
+// Everything a `Simulation` can be tuned with. Can be loaded from TOML or
+// JSON, where every field is optional and falls back to its default:
+//
+//     [world]
+//     animals = 40
+//
+//     [evolution]
+//     mutation_chance = 0.02
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimulationConfig {
+    pub world: WorldConfig,
+    pub animal: AnimalConfig,
+    pub eye: EyeConfig,
+    pub evolution: EvolutionConfig,
+    pub energy: EnergyConfig,
+    pub predators: PredatorConfig,
+    pub food: FoodConfig,
+    pub fitness: FitnessConfig,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorldConfig {
+    pub animals: usize,
+    pub foods: usize,
+    pub obstacles: Vec<Obstacle>,
+    pub boundary: Boundary,
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            animals: 10,
+            foods: 20,
+            obstacles: Vec::new(),
+            boundary: Boundary::Wrap,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnimalConfig {
+    pub speed_min: f32,
+    pub speed_max: f32,
+
+    // How much the brain can change speed and rotation in a single step
+    pub speed_accel: f32,
+    pub rotation_accel: f32,
+
+    // How close an animal has to get to food to eat it
+    pub eat_range: f32,
+}
+
+impl Default for AnimalConfig {
+    fn default() -> Self {
+        Self {
+            speed_min: 0.001,
+            speed_max: 0.005,
+            speed_accel: 0.2,
+            rotation_accel: FRAC_PI_2,
+            eat_range: 0.01,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EyeConfig {
+    pub fov_range: f32,
+    pub fov_angle: f32,
+
+    // Number of photoreceptors per channel
+    pub cells: usize,
+
+    // What the eye tells apart; the brain gets `cells` inputs for each
+    pub channels: Vec<Channel>,
+
+    pub model: VisionModel,
+
+    // How big things look to `VisionModel::Rays`
+    pub target_radius: f32,
+}
+
+impl Default for EyeConfig {
+    fn default() -> Self {
+        Self {
+            fov_range: 0.25,
+            fov_angle: PI + FRAC_PI_4,
+            cells: 9,
+            channels: vec![Channel::Food],
+            model: VisionModel::Sectors,
+            target_radius: 0.01,
+        }
+    }
+}
+
+// How the population evolves:
+//
+// - `generational` replaces every animal at once at the end of each
+//   generation, picking parents by their fitness (see `FitnessConfig`),
+//
+// - `open_ended` never stops the world; animals live off an energy budget
+//   (see `EnergyConfig`), die when it runs out and reproduce on their own
+//   once they've eaten enough, so the population's size is up to the
+//   environment. Statistics are still taken every `generation_length` steps.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    #[default]
+    Generational,
+    OpenEnded,
+}
+
+// How `mutation_chance`, `mutation_coeff` and `crossover_rate` change as
+// evolution goes on:
+//
+// - `fixed` - they don't,
+//
+// - `one_fifth` - Rechenberg's 1/5th success rule, scaling `mutation_coeff`
+//   by `factor` every `window` generations,
+//
+// - `diversity` - keeps the population's diversity around `target`, scaling
+//   `mutation_chance` and `crossover_rate` by `factor`.
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "model", rename_all = "snake_case", deny_unknown_fields)]
+pub enum RateControl {
+    #[default]
+    Fixed,
+    OneFifth { window: usize, factor: f32 },
+    Diversity { target: f32, factor: f32 },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EvolutionConfig {
+    pub mode: Mode,
+    pub mutation_chance: f32,
+    pub mutation_coeff: f32,
+    pub crossover_rate: f32,
+    pub rate_control: RateControl,
+
+    // Steps between two generations
+    pub generation_length: usize,
+
+    // Brain weights start out in -1..=1; mutation may take them this far
+    pub weight_limit: f32,
+
+    pub hall_of_fame_size: usize,
+
+    // Whether to keep the family tree of every animal (and predator) ever
+    // evolved, see `Simulation::genealogy`; it grows without bound, so it's
+    // off unless asked for
+    pub genealogy: bool,
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Generational,
+            mutation_chance: 0.01,
+            mutation_coeff: 0.3,
+            crossover_rate: 1.0,
+            rate_control: RateControl::Fixed,
+            generation_length: 2500,
+            weight_limit: 4.0,
+            hall_of_fame_size: 10,
+            genealogy: false,
+        }
+    }
+}
+
+// Energy budget of animals in `Mode::OpenEnded`; unused otherwise
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnergyConfig {
+    // What animals of the initial population start with
+    pub initial: f32,
+    pub max: f32,
+
+    // Gained per unit of nutrition eaten, see `FoodKind`
+    pub food: f32,
+
+    // Spent every step, plus `move_cost` per unit of distance travelled
+    pub idle_cost: f32,
+    pub move_cost: f32,
+
+    // Animals with at least `reproduction_threshold` energy give
+    // `reproduction_cost` of it to a child
+    pub reproduction_threshold: f32,
+    pub reproduction_cost: f32,
+
+    // In open-ended runs, animals don't reproduce while there's this many
+    // of them
+    pub max_population: usize,
+}
+
+impl Default for EnergyConfig {
+    fn default() -> Self {
+        Self {
+            initial: 1.0,
+            max: 2.0,
+            food: 0.4,
+            idle_cost: 0.0002,
+            move_cost: 0.1,
+            reproduction_threshold: 1.5,
+            reproduction_cost: 0.75,
+            max_population: 100,
+        }
+    }
+}
+
+// A second species hunting the animals, evolved by its own genetic
+// algorithm every `evolution.generation_length` steps (whatever the mode)
+// with fitness going by the animals caught; in `Mode::Generational` it
+// co-evolves with the animals, see `ga::CoEvolution`. Disabled when `count`
+// is zero.
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PredatorConfig {
+    pub count: usize,
+    pub animal: AnimalConfig,
+    pub eye: EyeConfig,
+
+    // How close a predator has to get to an animal to catch it
+    pub catch_range: f32,
+
+    // Satiation an animal loses when caught in `Mode::Generational`, after
+    // which it escapes to a random spot; in `Mode::OpenEnded` caught
+    // animals die instead
+    pub catch_penalty: usize,
+}
+
+impl Default for PredatorConfig {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            animal: AnimalConfig {
+                speed_max: 0.006,
+                ..AnimalConfig::default()
+            },
+            eye: EyeConfig {
+                channels: vec![Channel::Prey, Channel::Conspecifics],
+                ..EyeConfig::default()
+            },
+            catch_range: 0.01,
+            catch_penalty: 3,
+        }
+    }
+}
+
+// How food grows back once eaten, and how much of it there is over time;
+// by default every food is eaten in one go and immediately grows back
+// somewhere else:
+//
+//     [food]
+//     quantity = 3
+//     regrowth_delay = 200
+//
+//     [food.spawn]
+//     model = "patches"
+//     count = 4
+//     radius = 0.1
+//
+//     [[food.kinds]]
+//     nutrition = 1
+//
+//     [[food.kinds]]
+//     share = 0.2
+//     nutrition = -2
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FoodConfig {
+    pub spawn: FoodSpawn,
+
+    // Times a food can be eaten before it's used up
+    pub quantity: usize,
+
+    // Steps a used up food takes to grow back
+    pub regrowth_delay: usize,
+
+    // Over every `season_length` steps, the number of foods goes down to
+    // `1 - season_amplitude` of `world.foods` and back up again; no seasons
+    // when zero
+    pub season_length: usize,
+    pub season_amplitude: f32,
+
+    // What foods there are; eyes tell them apart with `Channel::FoodKind`
+    pub kinds: Vec<FoodKind>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FoodKind {
+    // How likely a food is to grow as this kind, relative to the others
+    pub share: f32,
+
+    // Satiation gained per bite, along with `nutrition * energy.food` of
+    // energy; toxic food has a negative one
+    pub nutrition: i32,
+}
+
+impl Default for FoodKind {
+    fn default() -> Self {
+        Self { share: 1.0, nutrition: 1 }
+    }
+}
+
+impl Default for FoodConfig {
+    fn default() -> Self {
+        Self {
+            spawn: FoodSpawn::Uniform,
+            quantity: 1,
+            regrowth_delay: 0,
+            season_length: 0,
+            season_amplitude: 0.5,
+            kinds: vec![FoodKind::default()],
+        }
+    }
+}
+
+// Fitness of animals, as a weighted sum of how much they've eaten
+// (satiation), how far they've gone, how much energy they've gotten per unit
+// spent, and how many steps they've lived; see `Metrics`. Predators always
+// go by the number of animals caught.
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FitnessConfig {
+    pub satiation: f32,
+    pub distance: f32,
+    pub efficiency: f32,
+    pub survival: f32,
+}
+
+impl Default for FitnessConfig {
+    fn default() -> Self {
+        Self {
+            satiation: 1.0,
+            distance: 0.0,
+            efficiency: 0.0,
+            survival: 0.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Parse(String),
+
+    // `field` is the path to the offending value, e.g. `eye.cells`
+    Invalid { field: &'static str, reason: &'static str },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read config: {}", err),
+            Self::Parse(err) => write!(f, "couldn't parse config: {}", err),
+            Self::Invalid { field, reason } => write!(f, "invalid `{}`: {}", field, reason),
+        }
+    }
+}
+
+impl error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl SimulationConfig {
+    pub fn from_toml(source: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(source)
+            .map_err(|err| ConfigError::Parse(err.to_string()))?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_json(source: &str) -> Result<Self, ConfigError> {
+        let config: Self = serde_json::from_str(source)
+            .map_err(|err| ConfigError::Parse(err.to_string()))?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    // Picks the format by the file's extension: `.json` is JSON, anything
+    // else is TOML
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&source),
+            _ => Self::from_toml(&source),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let Self { world, animal, eye, evolution, energy, predators, food, fitness } = self;
+
+        check(world.animals > 0, "world.animals", "must be at least 1")?;
+        check(
+            world.obstacles.iter().all(Obstacle::is_valid),
+            "world.obstacles",
+            "must have a positive size",
+        )?;
+        check(Obstacle::leave_room(world), "world.obstacles", "must leave room for animals and food")?;
+
+        animal.validate(["animal.speed_min", "animal.speed_max", "animal.speed_accel", "animal.rotation_accel", "animal.eat_range"])?;
+        eye.validate(["eye.fov_range", "eye.fov_angle", "eye.cells", "eye.channels", "eye.target_radius"])?;
+
+        check((0.0..=1.0).contains(&evolution.mutation_chance), "evolution.mutation_chance", "must be within [0, 1]")?;
+        check(evolution.mutation_coeff >= 0.0, "evolution.mutation_coeff", "must not be negative")?;
+        check((0.0..=1.0).contains(&evolution.crossover_rate), "evolution.crossover_rate", "must be within [0, 1]")?;
+        match evolution.rate_control {
+            RateControl::Fixed => {}
+            RateControl::OneFifth { window, factor } => {
+                check(window > 0, "evolution.rate_control.window", "must be at least 1")?;
+                check(factor > 1.0, "evolution.rate_control.factor", "must be above 1")?;
+            }
+            RateControl::Diversity { target, factor } => {
+                check(target >= 0.0, "evolution.rate_control.target", "must not be negative")?;
+                check(factor > 1.0, "evolution.rate_control.factor", "must be above 1")?;
+            }
+        }
+        check(evolution.generation_length > 0, "evolution.generation_length", "must be at least 1")?;
+        check(evolution.weight_limit > 0.0, "evolution.weight_limit", "must be positive")?;
+        check(evolution.hall_of_fame_size > 0, "evolution.hall_of_fame_size", "must be at least 1")?;
+
+        check(energy.initial > 0.0, "energy.initial", "must be positive")?;
+        check(energy.max >= energy.initial, "energy.max", "must not be below `initial`")?;
+        check(energy.food >= 0.0, "energy.food", "must not be negative")?;
+        check(energy.idle_cost >= 0.0, "energy.idle_cost", "must not be negative")?;
+        check(energy.move_cost >= 0.0, "energy.move_cost", "must not be negative")?;
+        check(energy.reproduction_threshold <= energy.max, "energy.reproduction_threshold", "must not be above `max`")?;
+        check(energy.reproduction_cost > 0.0, "energy.reproduction_cost", "must be positive")?;
+        check(
+            energy.reproduction_cost < energy.reproduction_threshold,
+            "energy.reproduction_cost",
+            "must be below `reproduction_threshold`",
+        )?;
+
+        // Generational runs keep the population at `world.animals` and never
+        // look at the cap
+        if evolution.mode == Mode::OpenEnded {
+            check(energy.max_population >= world.animals, "energy.max_population", "must not be below `world.animals`")?;
+        }
+
+        predators.animal.validate([
+            "predators.animal.speed_min",
+            "predators.animal.speed_max",
+            "predators.animal.speed_accel",
+            "predators.animal.rotation_accel",
+            "predators.animal.eat_range",
+        ])?;
+        predators.eye.validate([
+            "predators.eye.fov_range",
+            "predators.eye.fov_angle",
+            "predators.eye.cells",
+            "predators.eye.channels",
+            "predators.eye.target_radius",
+        ])?;
+        check(predators.catch_range > 0.0, "predators.catch_range", "must be positive")?;
+
+        match food.spawn {
+            FoodSpawn::Uniform => {}
+            FoodSpawn::Patches { count, radius } => {
+                check(count > 0, "food.spawn.count", "must be at least 1")?;
+                check(radius > 0.0, "food.spawn.radius", "must be positive")?;
+            }
+            FoodSpawn::NearExisting { radius } => {
+                check(radius > 0.0, "food.spawn.radius", "must be positive")?;
+            }
+        }
+        check(food.quantity > 0, "food.quantity", "must be at least 1")?;
+        check((0.0..=1.0).contains(&food.season_amplitude), "food.season_amplitude", "must be within [0, 1]")?;
+        check(!food.kinds.is_empty(), "food.kinds", "must not be empty")?;
+        check(food.kinds.iter().all(|kind| kind.share >= 0.0), "food.kinds", "must not have a negative share")?;
+        check(food.kinds.iter().any(|kind| kind.share > 0.0), "food.kinds", "must have a positive share")?;
+
+        let known = |channel: &Channel| match channel {
+            Channel::FoodKind(kind) => *kind < food.kinds.len(),
+            _ => true,
+        };
+
+        check(eye.channels.iter().all(known), "eye.channels", "must only refer to existing food kinds")?;
+        check(predators.eye.channels.iter().all(known), "predators.eye.channels", "must only refer to existing food kinds")?;
+
+        check(fitness.satiation >= 0.0, "fitness.satiation", "must not be negative")?;
+        check(fitness.distance >= 0.0, "fitness.distance", "must not be negative")?;
+        check(fitness.efficiency >= 0.0, "fitness.efficiency", "must not be negative")?;
+        check(fitness.survival >= 0.0, "fitness.survival", "must not be negative")?;
+
+        Ok(())
+    }
+}
+
+fn check(valid: bool, field: &'static str, reason: &'static str) -> Result<(), ConfigError> {
+    if valid { Ok(()) } else { Err(ConfigError::Invalid { field, reason }) }
+}
+
+// Both species have their own animal and eye settings, so these take the
+// paths to report their fields under
+
+impl AnimalConfig {
+    fn validate(&self, fields: [&'static str; 5]) -> Result<(), ConfigError> {
+        let [speed_min, speed_max, speed_accel, rotation_accel, eat_range] = fields;
+
+        check(self.speed_min >= 0.0, speed_min, "must not be negative")?;
+        check(self.speed_max >= self.speed_min, speed_max, "must not be below `speed_min`")?;
+        check(self.speed_accel >= 0.0, speed_accel, "must not be negative")?;
+        check(self.rotation_accel >= 0.0, rotation_accel, "must not be negative")?;
+        check(self.eat_range > 0.0, eat_range, "must be positive")
+    }
+}
+
+impl EyeConfig {
+    fn validate(&self, fields: [&'static str; 5]) -> Result<(), ConfigError> {
+        let [fov_range, fov_angle, cells, channels, target_radius] = fields;
+
+        check(self.fov_range > 0.0, fov_range, "must be positive")?;
+        check(self.fov_angle > 0.0 && self.fov_angle <= 2.0 * PI, fov_angle, "must be within (0, 2π]")?;
+        check(self.cells > 0, cells, "must be at least 1")?;
+        check(!self.channels.is_empty(), channels, "must not be empty")?;
+        check(self.target_radius > 0.0, target_radius, "must be positive")?;
+
+        check(
+            self.channels
+                .iter()
+                .enumerate()
+                .all(|(idx, channel)| !self.channels[..idx].contains(channel)),
+            channels,
+            "must not repeat a channel",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // What `source` is rejected with, as shown to the user
+    fn rejected(source: &str) -> String {
+        SimulationConfig::from_toml(source).unwrap_err().to_string()
+    }
+
+    mod formats {
+        use super::*;
+        use test_case::test_case;
+
+        #[test]
+        fn missing_fields_fall_back_to_defaults() {
+            let config = SimulationConfig::from_toml("
+                [world]
+                animals = 40
+
+                [evolution]
+                mutation_chance = 0.02
+            ").unwrap();
+
+            assert_eq!(config.world.animals, 40);
+            assert_eq!(config.world.foods, WorldConfig::default().foods);
+            assert_eq!(config.evolution.mutation_chance, 0.02);
+            assert_eq!(config.eye, EyeConfig::default());
+        }
+
+        #[test]
+        fn json_works_too() {
+            let json = SimulationConfig::from_json(r#"{ "eye": { "cells": 5, "channels": ["food", "conspecifics"] } }"#).unwrap();
+            assert_eq!(json.eye.cells, 5);
+            assert_eq!(json.eye.channels, vec![Channel::Food, Channel::Conspecifics]);
+
+            let err = SimulationConfig::from_json(r#"{ "evolution": { "mutation_chance": 1.5 } }"#).unwrap_err();
+            assert_eq!(err.to_string(), "invalid `evolution.mutation_chance`: must be within [0, 1]");
+        }
+
+        #[test_case("[eye]\ncels = 3" ; "unknown field")]
+        #[test_case("[eye]\nchannels = [\"walls\"]" ; "unknown channel")]
+        #[test_case("[world]\nboundary = \"open\"" ; "unknown boundary")]
+        fn typos_are_parse_errors(source: &str) {
+            assert!(matches!(SimulationConfig::from_toml(source), Err(ConfigError::Parse(_))));
+        }
+    }
+
+    mod world {
+        use super::*;
+        use test_case::test_case;
+
+        #[test]
+        fn obstacles_are_parsed() {
+            let config = SimulationConfig::from_toml("
+                [[world.obstacles]]
+                shape = \"segment\"
+                from = [0.5, 0.0]
+                to = [0.5, 1.0]
+
+                [[world.obstacles]]
+                shape = \"rect\"
+                min = [0.1, 0.1]
+                max = [0.2, 0.3]
+            ").unwrap();
+
+            assert_eq!(config.world.obstacles.len(), 2);
+            assert!(matches!(config.world.obstacles[1], Obstacle::Rect { .. }));
+        }
+
+        #[test]
+        fn boundary_is_parsed() {
+            let config = SimulationConfig::from_toml("[world]\nboundary = \"lethal\"").unwrap();
+            assert_eq!(config.world.boundary, Boundary::Lethal);
+        }
+
+        #[test_case(
+            "[world]\nanimals = 0",
+            "invalid `world.animals`: must be at least 1"
+            ; "no animals"
+        )]
+        #[test_case(
+            "[[world.obstacles]]\nshape = \"circle\"\ncenter = [0.5, 0.5]\nradius = 0.0",
+            "invalid `world.obstacles`: must have a positive size"
+            ; "empty obstacle"
+        )]
+        #[test_case(
+            "[[world.obstacles]]\nshape = \"rect\"\nmin = [-0.1, -0.1]\nmax = [1.1, 1.1]",
+            "invalid `world.obstacles`: must leave room for animals and food"
+            ; "obstacles everywhere"
+        )]
+        fn is_validated(source: &str, err: &str) {
+            assert_eq!(rejected(source), err);
+        }
+    }
+
+    mod animal {
+        use super::*;
+        use test_case::test_case;
+
+        #[test_case("[animal]\nspeed_min = -0.1", "invalid `animal.speed_min`: must not be negative" ; "speed_min")]
+        #[test_case(
+            "[animal]\nspeed_min = 0.01\nspeed_max = 0.001",
+            "invalid `animal.speed_max`: must not be below `speed_min`"
+            ; "speed_max"
+        )]
+        #[test_case("[animal]\nspeed_accel = -0.1", "invalid `animal.speed_accel`: must not be negative" ; "speed_accel")]
+        #[test_case("[animal]\nrotation_accel = -0.1", "invalid `animal.rotation_accel`: must not be negative" ; "rotation_accel")]
+        #[test_case("[animal]\neat_range = 0.0", "invalid `animal.eat_range`: must be positive" ; "eat_range")]
+        fn is_validated(source: &str, err: &str) {
+            assert_eq!(rejected(source), err);
+        }
+    }
+
+    mod eye {
+        use super::*;
+        use test_case::test_case;
+
+        #[test]
+        fn food_kind_channels_are_parsed() {
+            let config = SimulationConfig::from_toml("
+                [eye]
+                channels = [{ food_kind = 0 }, { food_kind = 1 }, \"predators\"]
+
+                [[food.kinds]]
+
+                [[food.kinds]]
+                share = 0.5
+            ").unwrap();
+
+            assert_eq!(config.eye.channels, vec![Channel::FoodKind(0), Channel::FoodKind(1), Channel::Predators]);
+        }
+
+        #[test_case("[eye]\nfov_range = 0.0", "invalid `eye.fov_range`: must be positive" ; "fov_range")]
+        #[test_case("[eye]\nfov_angle = 7.0", "invalid `eye.fov_angle`: must be within (0, 2π]" ; "fov_angle")]
+        #[test_case("[eye]\ncells = 0", "invalid `eye.cells`: must be at least 1" ; "cells")]
+        #[test_case("[eye]\nchannels = []", "invalid `eye.channels`: must not be empty" ; "no channels")]
+        #[test_case("[eye]\ntarget_radius = 0.0", "invalid `eye.target_radius`: must be positive" ; "target_radius")]
+        #[test_case(
+            "[eye]\nchannels = [\"food\", \"food\"]",
+            "invalid `eye.channels`: must not repeat a channel"
+            ; "repeated channel"
+        )]
+        #[test_case(
+            "[eye]\nchannels = [{ food_kind = 1 }]",
+            "invalid `eye.channels`: must only refer to existing food kinds"
+            ; "unknown food kind"
+        )]
+        fn is_validated(source: &str, err: &str) {
+            assert_eq!(rejected(source), err);
+        }
+    }
+
+    mod evolution {
+        use super::*;
+        use test_case::test_case;
+
+        #[test]
+        fn genealogy_is_opt_in() {
+            assert!(!SimulationConfig::default().evolution.genealogy);
+
+            let config = SimulationConfig::from_toml("[evolution]\ngenealogy = true").unwrap();
+            assert!(config.evolution.genealogy);
+        }
+
+        #[test]
+        fn rate_control_is_parsed() {
+            let config = SimulationConfig::from_toml("
+                [evolution.rate_control]
+                model = \"one_fifth\"
+                window = 5
+                factor = 1.5
+            ").unwrap();
+
+            assert_eq!(config.evolution.rate_control, RateControl::OneFifth { window: 5, factor: 1.5 });
+        }
+
+        #[test_case(
+            "[evolution]\nmutation_chance = 1.5",
+            "invalid `evolution.mutation_chance`: must be within [0, 1]"
+            ; "mutation_chance"
+        )]
+        #[test_case(
+            "[evolution]\nmutation_coeff = -0.1",
+            "invalid `evolution.mutation_coeff`: must not be negative"
+            ; "mutation_coeff"
+        )]
+        #[test_case(
+            "[evolution]\ncrossover_rate = -0.1",
+            "invalid `evolution.crossover_rate`: must be within [0, 1]"
+            ; "crossover_rate"
+        )]
+        #[test_case(
+            "[evolution.rate_control]\nmodel = \"one_fifth\"\nwindow = 0\nfactor = 2.0",
+            "invalid `evolution.rate_control.window`: must be at least 1"
+            ; "one_fifth window"
+        )]
+        #[test_case(
+            "[evolution.rate_control]\nmodel = \"one_fifth\"\nwindow = 5\nfactor = 0.5",
+            "invalid `evolution.rate_control.factor`: must be above 1"
+            ; "one_fifth factor"
+        )]
+        #[test_case(
+            "[evolution.rate_control]\nmodel = \"diversity\"\ntarget = -1.0\nfactor = 2.0",
+            "invalid `evolution.rate_control.target`: must not be negative"
+            ; "diversity target"
+        )]
+        #[test_case(
+            "[evolution.rate_control]\nmodel = \"diversity\"\ntarget = 0.1\nfactor = 1.0",
+            "invalid `evolution.rate_control.factor`: must be above 1"
+            ; "diversity factor"
+        )]
+        #[test_case(
+            "[evolution]\ngeneration_length = 0",
+            "invalid `evolution.generation_length`: must be at least 1"
+            ; "generation_length"
+        )]
+        #[test_case(
+            "[evolution]\nweight_limit = 0.0",
+            "invalid `evolution.weight_limit`: must be positive"
+            ; "weight_limit"
+        )]
+        #[test_case(
+            "[evolution]\nhall_of_fame_size = 0",
+            "invalid `evolution.hall_of_fame_size`: must be at least 1"
+            ; "hall_of_fame_size"
+        )]
+        fn is_validated(source: &str, err: &str) {
+            assert_eq!(rejected(source), err);
+        }
+    }
+
+    mod energy {
+        use super::*;
+        use test_case::test_case;
+
+        #[test]
+        fn missing_fields_fall_back_to_defaults() {
+            let config = SimulationConfig::from_toml("
+                [evolution]
+                mode = \"open_ended\"
+
+                [energy]
+                food = 0.5
+            ").unwrap();
+
+            assert_eq!(config.evolution.mode, Mode::OpenEnded);
+            assert_eq!(config.energy.food, 0.5);
+            assert_eq!(config.energy.max, EnergyConfig::default().max);
+        }
+
+        #[test_case("[energy]\ninitial = 0.0", "invalid `energy.initial`: must be positive" ; "initial")]
+        #[test_case("[energy]\nmax = 0.5", "invalid `energy.max`: must not be below `initial`" ; "max")]
+        #[test_case("[energy]\nfood = -0.1", "invalid `energy.food`: must not be negative" ; "food")]
+        #[test_case("[energy]\nidle_cost = -0.1", "invalid `energy.idle_cost`: must not be negative" ; "idle_cost")]
+        #[test_case("[energy]\nmove_cost = -0.1", "invalid `energy.move_cost`: must not be negative" ; "move_cost")]
+        #[test_case(
+            "[energy]\nreproduction_threshold = 3.0",
+            "invalid `energy.reproduction_threshold`: must not be above `max`"
+            ; "reproduction_threshold"
+        )]
+        #[test_case(
+            "[energy]\nreproduction_cost = 0.0",
+            "invalid `energy.reproduction_cost`: must be positive"
+            ; "free reproduction"
+        )]
+        #[test_case(
+            "[energy]\nreproduction_cost = 1.5",
+            "invalid `energy.reproduction_cost`: must be below `reproduction_threshold`"
+            ; "reproduction_cost"
+        )]
+        fn is_validated(source: &str, err: &str) {
+            assert_eq!(rejected(source), err);
+        }
+
+        #[test]
+        fn max_population_only_caps_open_ended_runs() {
+            let config = SimulationConfig::from_toml("[world]\nanimals = 200").unwrap();
+            assert_eq!(config.evolution.mode, Mode::Generational);
+
+            let err = rejected("
+                [world]
+                animals = 200
+
+                [evolution]
+                mode = \"open_ended\"
+            ");
+
+            assert_eq!(err, "invalid `energy.max_population`: must not be below `world.animals`");
+        }
+    }
+
+    mod predators {
+        use super::*;
+        use test_case::test_case;
+
+        #[test_case(
+            "[predators.animal]\nspeed_min = -0.1",
+            "invalid `predators.animal.speed_min`: must not be negative"
+            ; "animal"
+        )]
+        #[test_case("[predators.eye]\ncells = 0", "invalid `predators.eye.cells`: must be at least 1" ; "eye")]
+        #[test_case(
+            "[predators.eye]\nchannels = [\"prey\", \"prey\"]",
+            "invalid `predators.eye.channels`: must not repeat a channel"
+            ; "repeated channel"
+        )]
+        #[test_case("[predators]\ncatch_range = 0.0", "invalid `predators.catch_range`: must be positive" ; "catch_range")]
+        #[test_case(
+            "[predators.eye]\nchannels = [{ food_kind = 1 }]",
+            "invalid `predators.eye.channels`: must only refer to existing food kinds"
+            ; "unknown food kind"
+        )]
+        fn is_validated(source: &str, err: &str) {
+            assert_eq!(rejected(source), err);
+        }
+    }
+
+    mod food {
+        use super::*;
+        use test_case::test_case;
+
+        #[test]
+        fn spawn_is_parsed() {
+            let config = SimulationConfig::from_toml("
+                [food]
+                regrowth_delay = 200
+
+                [food.spawn]
+                model = \"near_existing\"
+                radius = 0.1
+            ").unwrap();
+
+            assert_eq!(config.food.regrowth_delay, 200);
+            assert_eq!(config.food.spawn, FoodSpawn::NearExisting { radius: 0.1 });
+            assert_eq!(config.food.quantity, FoodConfig::default().quantity);
+        }
+
+        #[test]
+        fn kinds_are_parsed() {
+            let config = SimulationConfig::from_toml("
+                [[food.kinds]]
+
+                [[food.kinds]]
+                share = 0.5
+                nutrition = -1
+            ").unwrap();
+
+            assert_eq!(config.food.kinds[0], FoodKind::default());
+            assert_eq!(config.food.kinds[1], FoodKind { share: 0.5, nutrition: -1 });
+        }
+
+        #[test_case(
+            "[food.spawn]\nmodel = \"patches\"\ncount = 0\nradius = 0.1",
+            "invalid `food.spawn.count`: must be at least 1"
+            ; "patch count"
+        )]
+        #[test_case(
+            "[food.spawn]\nmodel = \"patches\"\ncount = 1\nradius = 0.0",
+            "invalid `food.spawn.radius`: must be positive"
+            ; "patch radius"
+        )]
+        #[test_case(
+            "[food.spawn]\nmodel = \"near_existing\"\nradius = 0.0",
+            "invalid `food.spawn.radius`: must be positive"
+            ; "near_existing radius"
+        )]
+        #[test_case("[food]\nquantity = 0", "invalid `food.quantity`: must be at least 1" ; "quantity")]
+        #[test_case(
+            "[food]\nseason_amplitude = 1.5",
+            "invalid `food.season_amplitude`: must be within [0, 1]"
+            ; "season_amplitude"
+        )]
+        #[test_case("[food]\nkinds = []", "invalid `food.kinds`: must not be empty" ; "no kinds")]
+        #[test_case(
+            "[[food.kinds]]\nshare = -1.0\n\n[[food.kinds]]",
+            "invalid `food.kinds`: must not have a negative share"
+            ; "negative share"
+        )]
+        #[test_case(
+            "[[food.kinds]]\nshare = 0.0",
+            "invalid `food.kinds`: must have a positive share"
+            ; "no positive share"
+        )]
+        fn is_validated(source: &str, err: &str) {
+            assert_eq!(rejected(source), err);
+        }
+    }
+
+    mod fitness {
+        use super::*;
+        use test_case::test_case;
+
+        #[test]
+        fn missing_fields_fall_back_to_defaults() {
+            let config = SimulationConfig::from_toml("[fitness]\ndistance = 10.0").unwrap();
+
+            assert_eq!(config.fitness.distance, 10.0);
+            assert_eq!(config.fitness.satiation, FitnessConfig::default().satiation);
+        }
+
+        #[test_case("[fitness]\nsatiation = -1.0", "invalid `fitness.satiation`: must not be negative" ; "satiation")]
+        #[test_case("[fitness]\ndistance = -1.0", "invalid `fitness.distance`: must not be negative" ; "distance")]
+        #[test_case("[fitness]\nefficiency = -1.0", "invalid `fitness.efficiency`: must not be negative" ; "efficiency")]
+        #[test_case("[fitness]\nsurvival = -1.0", "invalid `fitness.survival`: must not be negative" ; "survival")]
+        fn is_validated(source: &str, err: &str) {
+            assert_eq!(rejected(source), err);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::*;
+use ga::Individual;
+
+impl Simulation {
+    // Scores already evolved brains (e.g. from a hall of fame): each gets an
+    // animal in a world built from `seed` and `config`, which then runs for
+    // one generation without evolving. Returns every animal's fitness (see
+    // `FitnessConfig`) at the end of it or when it died, in the order of
+    // `brains`.
+
+    pub fn evaluate(
+        seed: u64,
+        config: SimulationConfig,
+        brains: &[ga::Chromosome],
+    ) -> Result<Vec<f32>, ConfigError> {
+        let mut simulation = Self::with_config(seed, config)?;
+
+        let expected = Brain::chromosome_len(&Eye::new(&simulation.config.eye));
+
+        if brains.iter().any(|brain| brain.len() != expected) {
+            return Err(ConfigError::Invalid {
+                field: "eye.cells",
+                reason: "doesn't match the brains being evaluated",
+            });
+        }
+
+        // Lineages tell the evaluated animals apart from the ones born or
+        // restocked along the way in `Mode::OpenEnded`
+        let mut individuals: Vec<_> = brains
+            .iter()
+            .map(|brain| AnimalIndividual::create(brain.clone()))
+            .collect();
+
+        simulation.coevolution.ga_mut(PREY).assign_lineage(&mut individuals);
+
+        let ids: Vec<_> = individuals
+            .iter()
+            .filter_map(|individual| individual.lineage())
+            .map(|lineage| lineage.id)
+            .collect();
+
+        simulation.world.animals = individuals
+            .into_iter()
+            .map(|individual| individual.into_animal(&simulation.config, Species::Prey, &mut simulation.rng))
+            .collect();
+
+        let mut scores = vec![0.0; brains.len()];
+
+        let mut score = |simulation: &Simulation, animal: &Animal| {
+            let Some(lineage) = &animal.lineage else {
+                return;
+            };
+
+            if let Some(idx) = ids.iter().position(|&id| id == lineage.id) {
+                scores[idx] = simulation.fitness().fitness(animal).max(0.0);
+            }
+        };
+
+        for _ in 0..simulation.config.evolution.generation_length {
+            for animal in simulation.tick() {
+                score(&simulation, &animal);
+            }
+        }
+
+        for animal in &simulation.world.animals {
+            score(&simulation, animal);
+        }
+
+        Ok(scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(generation_length: usize) -> SimulationConfig {
+        SimulationConfig {
+            evolution: EvolutionConfig { generation_length, ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scores_every_brain() {
+        let mut simulation = Simulation::with_config(1, config(300)).unwrap();
+        simulation.train();
+
+        let brains: Vec<_> = simulation
+            .hall_of_fame()
+            .entries()
+            .iter()
+            .map(|entry| entry.chromosome.clone())
+            .collect();
+
+        let scores = Simulation::evaluate(7, config(300), &brains).unwrap();
+
+        assert_eq!(scores.len(), brains.len());
+        assert_eq!(scores, Simulation::evaluate(7, config(300), &brains).unwrap());
+    }
+
+    fn brains() -> Vec<ga::Chromosome> {
+        Simulation::from_seed(0).world.animals.iter().map(Animal::as_chromosome).collect()
+    }
+
+    #[test]
+    fn scores_by_the_configured_fitness() {
+        let config = SimulationConfig {
+            fitness: FitnessConfig { satiation: 0.0, distance: 1.0, ..Default::default() },
+            ..config(10)
+        };
+
+        let scores = Simulation::evaluate(0, config, &brains()).unwrap();
+
+        // Everyone moves, if not everyone eats
+        assert!(scores.iter().all(|&score| score > 0.0));
+    }
+
+    #[test]
+    fn scores_animals_that_died_too() {
+        let config = SimulationConfig {
+            evolution: EvolutionConfig { mode: Mode::OpenEnded, ..config(50).evolution },
+            energy: EnergyConfig { initial: 0.001, ..Default::default() },
+            fitness: FitnessConfig { satiation: 0.0, survival: 1.0, ..Default::default() },
+            ..Default::default()
+        };
+
+        let scores = Simulation::evaluate(0, config, &brains()).unwrap();
+
+        assert_eq!(scores.len(), 10);
+        assert!(scores.iter().all(|&score| score > 0.0 && score < 50.0));
+    }
+
+    #[test]
+    fn brains_must_fit_the_eye() {
+        let brains = vec![ga::Chromosome::from_iter([0.0; 10])];
+
+        assert!(matches!(
+            Simulation::evaluate(0, config(10), &brains),
+            Err(ConfigError::Invalid { field: "eye.cells", .. }),
+        ));
+    }
+}
@@ -1,94 +1,410 @@
 use crate::*;
+use serde::{Deserialize, Serialize};
 use std::f32::consts::*;
 
-const FOV_RANGE: f32 = 0.25;
+// Anything an eye can be shown
 
-const FOV_ANGLE: f32 = PI + FRAC_PI_4;
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Entity {
+    // Of the given kind, see `FoodConfig::kinds`
+    Food(usize),
+    Animal(Species),
+    Obstacle,
+}
+
+// A kind of thing an eye can see, each perceived by its own set of cells.
+// `Food` is food of any kind, while `FoodKind` only sees the food kind of
+// the given index (e.g. `{ food_kind = 1 }`). `Prey` and `Predators` are the
+// two species, whichever one is looking; `Conspecifics` is whichever the
+// looking animal belongs to. Animals never see themselves.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Channel {
+    Food,
+    FoodKind(usize),
+    Conspecifics,
+    Prey,
+    Predators,
+    Obstacles,
+}
 
-const CELLS: usize = 9;
+impl Channel {
+    // Whether this channel shows `entity` to an animal of `viewer`'s species
+
+    pub fn shows(self, entity: Entity, viewer: Species) -> bool {
+        match (self, entity) {
+            (Self::Food, Entity::Food(_)) => true,
+            (Self::FoodKind(kind), Entity::Food(other)) => kind == other,
+            (Self::Prey, Entity::Animal(Species::Prey)) => true,
+            (Self::Predators, Entity::Animal(Species::Predator)) => true,
+            (Self::Conspecifics, Entity::Animal(species)) => species == viewer,
+            (Self::Obstacles, Entity::Obstacle) => true,
+            _ => false,
+        }
+    }
+}
+
+// How cells see:
+//
+// - `sectors` splits the field of view into one sector per cell, each
+//   summing up how close everything within it is (the closest point of
+//   obstacles); things don't hide each other,
+//
+// - `rays` casts one ray per cell, through the middle of its sector, which
+//   only reports the nearest thing it hits; anything - whether the eye has
+//   a channel for it or not - hides whatever's behind it.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VisionModel {
+    #[default]
+    Sectors,
+    Rays,
+}
 
 #[derive(Debug)]
 pub struct Eye{
     fov_range: f32,
     fov_angle: f32,
     cells: usize,
+    channels: Vec<Channel>,
+    model: VisionModel,
+    target_radius: f32,
 }
 
 
 impl Eye {
-    fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
+    pub fn new(config: &EyeConfig) -> Self {
 
         // checks preconditions 
-        assert!(fov_range > 0.0);
-        assert!(fov_angle > 0.0);
-        assert!(cells > 0);
+        assert!(config.fov_range > 0.0);
+        assert!(config.fov_angle > 0.0);
+        assert!(config.cells > 0);
+        assert!(!config.channels.is_empty());
+        assert!(config.target_radius > 0.0);
 
-        Self { fov_range, fov_angle, cells }
+        Self {
+            fov_range: config.fov_range,
+            fov_angle: config.fov_angle,
+            cells: config.cells,
+            channels: config.channels.clone(),
+            model: config.model,
+            target_radius: config.target_radius,
+        }
     }
 
+    pub fn fov_range(&self) -> f32 {
+        self.fov_range
+    }
+
+    // How far away something can be and still be seen; rays can also catch
+    // the edge of things just out of range
+
+    pub fn reach(&self) -> f32 {
+        match self.model {
+            VisionModel::Sectors => self.fov_range,
+            VisionModel::Rays => self.fov_range + self.target_radius,
+        }
+    }
+
+    // Cells per channel
+
     pub fn cells(&self) -> usize {
         self.cells
     }
 
-    pub fn process_vision(
+    pub fn channels(&self) -> &[Channel] {
+        &self.channels
+    }
+
+    pub fn model(&self) -> VisionModel {
+        self.model
+    }
+
+    // Length of what `perceive` returns
+
+    pub fn inputs(&self) -> usize {
+        self.cells * self.channels.len()
+    }
+
+    // What an animal of `viewer`'s species sees of `entities` and
+    // `obstacles`, channel by channel: the cells of the first channel come
+    // first, then those of the second one and so on.
+
+    pub fn perceive(
         &self,
         position: na::Point2<f32>,
         rotation: na::Rotation2<f32>,
-        foods: &[Food],
+        viewer: Species,
+        entities: impl IntoIterator<Item = (Entity, na::Point2<f32>)>,
+        obstacles: &[Obstacle],
     ) -> Vec<f32> {
-        let mut cells = vec![0.0; self.cells];
+        let mut inputs = vec![0.0; self.inputs()];
 
-        for food in foods {
-            let relative_pos = food.position - position;
+        match self.model {
+            VisionModel::Sectors => {
+                let obstacles = obstacles
+                    .iter()
+                    .map(|obstacle| (Entity::Obstacle, obstacle.closest_point(position)))
+                    .filter(|(_, closest)| *closest != position);
 
-            let dist = relative_pos.norm();
+                for (entity, target) in entities.into_iter().chain(obstacles) {
+                    if let Some((cell, energy)) = self.sector(position, rotation, target) {
+                        self.activate(&mut inputs, cell, entity, viewer, |input| *input += energy);
+                    }
+                }
+            }
 
-            if dist>= self.fov_range {
-                continue;
+            VisionModel::Rays => {
+                let entities: Vec<_> = entities.into_iter().collect();
+
+                for cell in 0..self.cells {
+                    if let Some((entity, dist)) = self.cast_ray(position, rotation, cell, &entities, obstacles) {
+                        let energy = (self.fov_range - dist) / self.fov_range;
+
+                        self.activate(&mut inputs, cell, entity, viewer, |input| *input = energy);
+                    }
+                }
             }
+        }
 
-            let angle = na::Rotation2::rotation_between(
-                &na::Vector2::x(),
-                &relative_pos,
-            ).angle();
+        inputs
+    }
 
-            // now we include the bird's rotation
+    fn activate(
+        &self,
+        inputs: &mut [f32],
+        cell: usize,
+        entity: Entity,
+        viewer: Species,
+        mut update: impl FnMut(&mut f32),
+    ) {
+        for (idx, channel) in self.channels.iter().enumerate() {
+            if channel.shows(entity, viewer) {
+                update(&mut inputs[idx * self.cells + cell]);
+            }
+        }
+    }
 
-            let angle = angle - rotation.angle();
+    // Which sector `target` falls in, if any, and how close it is
 
-            // and make it wrap around 
+    fn sector(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        target: na::Point2<f32>,
+    ) -> Option<(usize, f32)> {
+        let relative_pos = target - position;
 
-            let angle = na::wrap(angle, -PI, PI);
+        let dist = relative_pos.norm();
 
-            if angle < -self.fov_angle / 2.0 ||
-               angle > self.fov_angle / 2.0 
-            {
-                continue;
-            }
+        if dist>= self.fov_range {
+            return None;
+        }
 
-            // now we make angle relative to bird.s FOV
-            let angle = angle + self.fov_angle / 2.0;
-            
-            // we can then extract the index of the activated cell from the angle
+        let angle = na::Rotation2::rotation_between(
+            &na::Vector2::x(),
+            &relative_pos,
+        ).angle();
 
-            let cell_index = (angle/ self.fov_angle) * (self.cells as f32);
+        // now we include the bird's rotation
 
-            // convert it to usize
-            // and make sure it's within the bounds of the array
+        let angle = angle - rotation.angle();
 
-            let cell_index = (cell_index as usize).min(cells.len() - 1 );
+        // and make it wrap around 
 
-            let energy = (self.fov_range - dist) / self.fov_range ;
+        let angle = na::wrap(angle, -PI, PI);
 
-            cells[cell_index] += energy;
+        if angle < -self.fov_angle / 2.0 ||
+           angle > self.fov_angle / 2.0 
+        {
+            return None;
         }
-        cells 
+
+        // now we make angle relative to bird.s FOV
+        let angle = angle + self.fov_angle / 2.0;
+        
+        // we can then extract the index of the activated cell from the angle
+
+        let cell_index = (angle/ self.fov_angle) * (self.cells as f32);
+
+        // convert it to usize
+        // and make sure it's within the bounds of the array
+
+        let cell_index = (cell_index as usize).min(self.cells - 1 );
+
+        let energy = (self.fov_range - dist) / self.fov_range ;
+
+        Some((cell_index, energy))
+    }
+
+    // Nearest thing the ray through the middle of `cell`'s sector hits
+    // within range, and how far it is; entities are discs of
+    // `target_radius`
+
+    fn cast_ray(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        cell: usize,
+        entities: &[(Entity, na::Point2<f32>)],
+        obstacles: &[Obstacle],
+    ) -> Option<(Entity, f32)> {
+        let angle = rotation.angle() - self.fov_angle / 2.0
+            + (cell as f32 + 0.5) * self.fov_angle / self.cells as f32;
+
+        let direction = na::Vector2::new(angle.cos(), angle.sin());
+        let radius = self.target_radius;
+
+        let mut nearest = None;
+        let mut nearest_dist = self.fov_range;
+
+        for &(entity, target) in entities {
+            let relative_pos = target - position;
+
+            // Distance along the ray to the point closest to the target,
+            // and how far off the ray the target is (squared)
+            let along = relative_pos.dot(&direction);
+            let off = relative_pos.norm_squared() - along * along;
+
+            if along < 0.0 || off > radius * radius {
+                continue;
+            }
+
+            let dist = (along - (radius * radius - off).sqrt()).max(0.0);
+
+            if dist < nearest_dist {
+                nearest = Some(entity);
+                nearest_dist = dist;
+            }
+        }
+
+        for obstacle in obstacles {
+            if let Some(dist) = obstacle.ray_hit(position, direction) {
+                if dist < nearest_dist {
+                    nearest = Some(Entity::Obstacle);
+                    nearest_dist = dist;
+                }
+            }
+        }
+
+        nearest.map(|entity| (entity, nearest_dist))
     }
 }
 
 impl Default for Eye {
     fn default() -> Self {
-        Self::new(FOV_RANGE, FOV_ANGLE, CELLS)
+        Self::new(&EyeConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eye(channels: Vec<Channel>, model: VisionModel) -> Eye {
+        Eye::new(&EyeConfig {
+            cells: 3,
+            channels,
+            model,
+            ..EyeConfig::default()
+        })
+    }
+
+    // What an animal at (0.5, 0.5) sees of food in the middle of its field
+    // of view, with another animal right in front of it
+    fn perceive(eye: &Eye) -> Vec<f32> {
+        let entities = [
+            (Entity::Food(0), na::Point2::new(0.6, 0.5)),
+            (Entity::Animal(Species::Prey), na::Point2::new(0.55, 0.5)),
+        ];
+
+        eye.perceive(
+            na::Point2::new(0.5, 0.5),
+            na::Rotation2::new(0.0),
+            Species::Prey,
+            entities,
+            &[],
+        )
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-5, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn channels_are_seen_separately() {
+        let eye = eye(vec![Channel::Food, Channel::Conspecifics, Channel::Predators], VisionModel::Sectors);
+        let vision = perceive(&eye);
+
+        assert_eq!(vision.len(), eye.inputs());
+
+        assert_close(vision[1], (0.25 - 0.1) / 0.25);
+        assert_close(vision[3 + 1], (0.25 - 0.05) / 0.25);
+
+        for (idx, cell) in vision.iter().enumerate() {
+            if idx != 1 && idx != 3 + 1 {
+                assert_eq!(*cell, 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn food_kinds_are_told_apart() {
+        let eye = eye(vec![Channel::FoodKind(0), Channel::FoodKind(1), Channel::Food], VisionModel::Sectors);
+
+        let vision = eye.perceive(
+            na::Point2::new(0.5, 0.5),
+            na::Rotation2::new(0.0),
+            Species::Prey,
+            [(Entity::Food(1), na::Point2::new(0.6, 0.5))],
+            &[],
+        );
+
+        assert_eq!(vision[1], 0.0);
+        assert_close(vision[3 + 1], (0.25 - 0.1) / 0.25);
+        assert_close(vision[6 + 1], (0.25 - 0.1) / 0.25);
+    }
+
+    #[test]
+    fn rays_only_see_the_nearest_hit() {
+        let eye = eye(vec![Channel::Food, Channel::Conspecifics], VisionModel::Rays);
+        let vision = perceive(&eye);
+
+        // The animal hides the food behind it
+        assert!(vision[..3].iter().all(|&cell| cell == 0.0));
+        assert_close(vision[3 + 1], (0.25 - 0.04) / 0.25);
+
+        // Even from eyes that can't see animals
+        let eye = self::eye(vec![Channel::Food], VisionModel::Rays);
+        assert!(perceive(&eye).iter().all(|&cell| cell == 0.0));
+    }
+
+    #[test]
+    fn obstacles_are_seen_and_hide_things() {
+        let walls = [Obstacle::Segment {
+            from: na::Point2::new(0.52, 0.0),
+            to: na::Point2::new(0.52, 1.0),
+        }];
+
+        let see = |model| {
+            eye(vec![Channel::Food, Channel::Obstacles], model).perceive(
+                na::Point2::new(0.5, 0.5),
+                na::Rotation2::new(0.0),
+                Species::Prey,
+                [(Entity::Food(0), na::Point2::new(0.6, 0.5))],
+                &walls,
+            )
+        };
+
+        let sectors = see(VisionModel::Sectors);
+        assert!(sectors[1] > 0.0);
+        assert_close(sectors[3 + 1], (0.25 - 0.02) / 0.25);
+
+        let rays = see(VisionModel::Rays);
+        assert_eq!(rays[1], 0.0);
+        assert_close(rays[3 + 1], (0.25 - 0.02) / 0.25);
     }
 }
 
@@ -0,0 +1,179 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+// How well an animal has done, for the genetic algorithm to pick parents
+// by. `FitnessConfig` covers the usual measures; anything else can be a
+// closure, see `Simulation::with_fitness`:
+//
+//     simulation.with_fitness(|animal: &Animal| animal.metrics().distance)
+//
+// Negative results count as zero.
+
+pub trait Fitness: Send + Sync {
+    fn fitness(&self, animal: &Animal) -> f32;
+}
+
+impl<F> Fitness for F
+where
+    F: Fn(&Animal) -> f32 + Send + Sync,
+{
+    fn fitness(&self, animal: &Animal) -> f32 {
+        self(animal)
+    }
+}
+
+// What an animal has been up to during its life, kept track of whatever
+// the fitness
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Metrics {
+    // Steps lived
+    pub age: usize,
+
+    // Covered by every step taken
+    pub distance: f32,
+
+    // Gotten from food, lost to toxic food (see `FoodKind::nutrition`), and
+    // spent living and moving as per `EnergyConfig` - whether or not the mode
+    // actually drains it
+    pub energy_gained: f32,
+    pub energy_lost: f32,
+    pub energy_spent: f32,
+}
+
+impl Metrics {
+    // Net energy gained from food per unit spent; never below zero, even for
+    // animals that ate mostly toxic food
+
+    pub fn efficiency(&self) -> f32 {
+        if self.energy_spent > 0.0 {
+            (self.energy_gained - self.energy_lost).max(0.0) / self.energy_spent
+        } else {
+            0.0
+        }
+    }
+}
+
+// Weighted sum of the measures of `FitnessConfig`
+
+impl Fitness for FitnessConfig {
+    fn fitness(&self, animal: &Animal) -> f32 {
+        let metrics = animal.metrics();
+
+        self.satiation * animal.satiation() as f32
+            + self.distance * metrics.distance
+            + self.efficiency * metrics.efficiency()
+            + self.survival * metrics.age as f32
+    }
+}
+
+impl Simulation {
+    // Evolves animals by `fitness` instead of `config.fitness`. Closures
+    // can't be checkpointed, so resumed simulations need this again.
+
+    pub fn with_fitness(mut self, fitness: impl Fitness + 'static) -> Self {
+        self.fitness = Some(Box::new(fitness));
+        self
+    }
+
+    pub(crate) fn fitness(&self) -> &dyn Fitness {
+        match &self.fitness {
+            Some(fitness) => fitness.as_ref(),
+            None => &self.config.fitness,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SimulationConfig {
+        SimulationConfig {
+            evolution: EvolutionConfig { generation_length: 10, ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn metrics_are_tracked() {
+        let mut simulation = Simulation::with_config(0, config()).unwrap();
+
+        for _ in 0..5 {
+            simulation.step();
+        }
+
+        for animal in simulation.world().animals() {
+            let metrics = animal.metrics();
+
+            assert_eq!(metrics.age, 5);
+            assert!(metrics.distance > 0.0);
+            assert!(metrics.energy_spent > 0.0);
+        }
+    }
+
+    #[test]
+    fn measures_are_weighted() {
+        let mut simulation = Simulation::with_config(0, config()).unwrap();
+        simulation.step();
+
+        let animal = &mut simulation.world.animals[0];
+        animal.satiation = 2;
+        animal.metrics.energy_gained = 0.4;
+
+        let fitness = FitnessConfig {
+            satiation: 1.0,
+            distance: 0.0,
+            efficiency: 0.5,
+            survival: 2.0,
+        };
+
+        let efficiency = 0.4 / animal.metrics.energy_spent;
+        let expected = 2.0 + 0.5 * efficiency + 2.0;
+
+        assert!((fitness.fitness(animal) - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn toxic_food_doesnt_make_fitness_negative() {
+        let mut simulation = Simulation::with_config(0, SimulationConfig {
+            food: FoodConfig {
+                kinds: vec![FoodKind { share: 1.0, nutrition: -2 }],
+                ..Default::default()
+            },
+            ..config()
+        })
+        .unwrap();
+
+        simulation.step();
+
+        let position = simulation.world.animals[0].position;
+        simulation.world.foods[0].position = position;
+        simulation.process_collisions();
+
+        let animal = &simulation.world.animals[0];
+        assert_eq!(animal.metrics.energy_gained, 0.0);
+        assert!(animal.metrics.energy_lost > 0.0);
+
+        let fitness = FitnessConfig {
+            satiation: 0.0,
+            efficiency: 1.0,
+            ..Default::default()
+        };
+
+        assert_eq!(fitness.fitness(animal), 0.0);
+    }
+
+    #[test]
+    fn custom_fitness_drives_evolution() {
+        let mut simulation = Simulation::with_config(0, config())
+            .unwrap()
+            .with_fitness(|animal: &Animal| animal.metrics().distance);
+
+        let stats = simulation.train();
+
+        // Every animal moves at most `speed_max` a step
+        assert!(stats.min_fitness() > 0.0);
+        assert!(stats.max_fitness() <= 11.0 * simulation.config().animal.speed_max + 1e-5);
+    }
+}
@@ -1,19 +1,333 @@
 use crate::*;
 use na::Point2;
+use rand::distributions::{Distribution, WeightedIndex};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
 
+// Where food grows, see `FoodConfig`:
+//
+// - `uniform` - anywhere,
+//
+// - `patches` - within `radius` of one of `count` spots, picked at random
+//   when the world is created,
+//
+// - `near_existing` - within `radius` of some other food that's grown, so
+//   food spreads out from where it already is.
 
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "model", rename_all = "snake_case", deny_unknown_fields)]
+pub enum FoodSpawn {
+    #[default]
+    Uniform,
+    Patches { count: usize, radius: f32 },
+    NearExisting { radius: f32 },
+}
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Food{
     pub(crate) position: Point2<f32>,
+
+    // Index into `FoodConfig::kinds`
+    pub(crate) kind: usize,
+
+    // Times it can still be eaten
+    pub(crate) bites: usize,
+
+    // Steps until it grows back, once used up
+    pub(crate) regrows_in: usize,
+
+    // Whether the current season has room for it
+    pub(crate) in_season: bool,
 }
 
 
 impl Food {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-        Self { position: rng.gen() }
+    // Anywhere but inside an obstacle
+
+    pub fn random(config: &SimulationConfig, rng: &mut dyn RngCore) -> Self {
+        let position = Obstacle::free_position(&config.world, rng)
+            .expect("obstacles are validated to leave room");
+
+        Self::grown(config, position, Self::pick_kind(config, rng))
+    }
+
+    pub(crate) fn grown(config: &SimulationConfig, position: Point2<f32>, kind: usize) -> Self {
+        Self {
+            position,
+            kind,
+            bites: config.food.quantity,
+            regrows_in: 0,
+            in_season: true,
+        }
     }
+
+    // A fresh set of `world.foods` foods, placed one after another
+
+    pub(crate) fn sow(config: &SimulationConfig, patches: &[Point2<f32>], rng: &mut dyn RngCore) -> Vec<Self> {
+        let mut foods = Vec::with_capacity(config.world.foods);
+
+        for _ in 0..config.world.foods {
+            let position = Self::spot(config, patches, &foods, rng);
+            let kind = Self::pick_kind(config, rng);
+
+            foods.push(Self::grown(config, position, kind));
+        }
+
+        foods
+    }
+
+    // Where a food grows (back), according to `FoodConfig::spawn`
+
+    pub(crate) fn spot(
+        config: &SimulationConfig,
+        patches: &[Point2<f32>],
+        foods: &[Food],
+        rng: &mut dyn RngCore,
+    ) -> Point2<f32> {
+        let around = match config.food.spawn {
+            FoodSpawn::Uniform => None,
+            FoodSpawn::Patches { radius, .. } => Some((patches[rng.gen_range(0..patches.len())], radius)),
+
+            FoodSpawn::NearExisting { radius } => {
+                let grown: Vec<_> = foods.iter().filter(|food| food.is_available()).collect();
+
+                // The first food, or the one after a famine, can't be near
+                // anything
+                (!grown.is_empty()).then(|| (grown[rng.gen_range(0..grown.len())].position, radius))
+            }
+        };
+
+        match around {
+            Some((center, radius)) => Self::near(config, center, radius, rng),
+            None => Obstacle::free_position(&config.world, rng)
+                .expect("obstacles are validated to leave room"),
+        }
+    }
+
+    // By `FoodKind::share`
+
+    pub(crate) fn pick_kind(config: &SimulationConfig, rng: &mut dyn RngCore) -> usize {
+        let kinds = &config.food.kinds;
+
+        if kinds.len() == 1 {
+            return 0;
+        }
+
+        WeightedIndex::new(kinds.iter().map(|kind| kind.share))
+            .expect("food kinds are validated")
+            .sample(rng)
+    }
+
+    // Uniformly within `radius` of `center`, trying a few times to miss
+    // obstacles before giving up on `center` altogether
+
+    fn near(config: &SimulationConfig, center: Point2<f32>, radius: f32, rng: &mut dyn RngCore) -> Point2<f32> {
+        let obstacles = config.world.boundary.repeat(&config.world.obstacles);
+
+        for _ in 0..100 {
+            let angle = rng.gen_range(0.0..2.0 * PI);
+            let distance = radius * rng.gen::<f32>().sqrt();
+            let position = center + distance * na::Vector2::new(angle.cos(), angle.sin());
+
+            let position = match config.world.boundary {
+                Boundary::Wrap => position.map(|axis| na::wrap(axis, 0.0, 1.0)),
+                _ => position.map(|axis| axis.clamp(0.0, 1.0)),
+            };
+
+            if !obstacles.iter().any(|obstacle| obstacle.contains(position)) {
+                return position;
+            }
+        }
+
+        Obstacle::free_position(&config.world, rng).expect("obstacles are validated to leave room")
+    }
+
     pub fn position(&self) -> Point2<f32> {
         self.position
     }
-}
\ No newline at end of file
+
+    // Whether it can be seen and eaten right now
+
+    pub fn is_available(&self) -> bool {
+        self.bites > 0 && self.regrows_in == 0 && self.in_season
+    }
+
+    pub fn kind(&self) -> usize {
+        self.kind
+    }
+
+    pub fn bites(&self) -> usize {
+        self.bites
+    }
+}
+
+// Share of `world.foods` in season after `steps` steps: one when seasons
+// are disabled, otherwise going from one down to `1 - season_amplitude` and
+// back every `season_length` steps
+
+pub(crate) fn abundance(config: &FoodConfig, steps: usize) -> f32 {
+    if config.season_length == 0 {
+        return 1.0;
+    }
+
+    let phase = (steps % config.season_length) as f32 / config.season_length as f32;
+
+    1.0 - config.season_amplitude * (1.0 - (2.0 * PI * phase).cos()) / 2.0
+}
+
+impl Simulation {
+    // Used up foods grow back once `regrowth_delay` is over, and seasons
+    // decide how many foods there are
+
+    pub(crate) fn process_food(&mut self) {
+        let in_season = (abundance(&self.config.food, self.steps) * self.world.foods.len() as f32).ceil() as usize;
+
+        for idx in 0..self.world.foods.len() {
+            self.world.foods[idx].in_season = idx < in_season;
+
+            if self.world.foods[idx].regrows_in == 0 {
+                continue;
+            }
+
+            self.world.foods[idx].regrows_in -= 1;
+
+            if self.world.foods[idx].regrows_in == 0 {
+                self.regrow(idx);
+            }
+        }
+    }
+
+    pub(crate) fn regrow(&mut self, idx: usize) {
+        let position = Food::spot(&self.config, &self.world.patches, &self.world.foods, &mut self.rng);
+        let kind = Food::pick_kind(&self.config, &mut self.rng);
+        let food = &mut self.world.foods[idx];
+
+        food.position = position;
+        food.kind = kind;
+        food.bites = self.config.food.quantity;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(food: FoodConfig) -> SimulationConfig {
+        SimulationConfig { food, ..Default::default() }
+    }
+
+    #[test]
+    fn patches_keep_food_together() {
+        let config = config(FoodConfig {
+            spawn: FoodSpawn::Patches { count: 2, radius: 0.05 },
+            ..Default::default()
+        });
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let world = World::random(&config, &mut rng);
+
+        assert_eq!(world.patches.len(), 2);
+
+        for food in world.foods() {
+            assert!(world
+                .patches
+                .iter()
+                .any(|&patch| Boundary::Wrap.distance(patch, food.position()) <= 0.05 + 1e-5));
+        }
+    }
+
+    #[test]
+    fn eaten_food_regrows_after_a_delay() {
+        let mut simulation = Simulation::with_config(0, config(FoodConfig {
+            quantity: 2,
+            regrowth_delay: 3,
+            ..Default::default()
+        }))
+        .unwrap();
+
+        let position = simulation.world.animals[0].position;
+        simulation.world.foods[0].position = position;
+
+        // Twice, since it's got two bites
+        simulation.process_collisions();
+        assert_eq!(simulation.world.foods[0].bites(), 1);
+        assert!(simulation.world.foods[0].is_available());
+
+        simulation.process_collisions();
+        assert!(!simulation.world.foods[0].is_available());
+
+        for _ in 0..2 {
+            simulation.process_food();
+            assert!(!simulation.world.foods[0].is_available());
+        }
+
+        simulation.process_food();
+
+        let food = &simulation.world.foods[0];
+        assert!(food.is_available());
+        assert_eq!(food.bites(), 2);
+        assert_ne!(food.position(), position);
+    }
+
+    #[test]
+    fn toxic_food_hurts() {
+        let mut simulation = Simulation::with_config(0, config(FoodConfig {
+            kinds: vec![
+                FoodKind::default(),
+                FoodKind { share: 0.5, nutrition: -2 },
+            ],
+            ..Default::default()
+        }))
+        .unwrap();
+
+        let kinds: Vec<_> = simulation.world.foods().iter().map(Food::kind).collect();
+        assert!(kinds.contains(&0) && kinds.contains(&1));
+
+        let animal = &mut simulation.world.animals[0];
+        animal.satiation = 3;
+        animal.energy = 1.0;
+
+        let position = animal.position;
+        let food = &mut simulation.world.foods[0];
+        food.position = position;
+        food.kind = 1;
+
+        simulation.process_collisions();
+        assert_eq!(simulation.world.animals[0].satiation, 1);
+        assert!((simulation.world.animals[0].energy - 0.2).abs() < 1e-5);
+
+        // Satiation doesn't go below zero
+        let food = &mut simulation.world.foods[0];
+        food.position = position;
+        food.kind = 1;
+
+        simulation.process_collisions();
+        assert_eq!(simulation.world.animals[0].satiation, 0);
+    }
+
+    #[test]
+    fn seasons_change_abundance() {
+        let config = FoodConfig {
+            season_length: 100,
+            season_amplitude: 0.5,
+            ..Default::default()
+        };
+
+        assert_eq!(abundance(&config, 0), 1.0);
+        assert!((abundance(&config, 50) - 0.5).abs() < 1e-5);
+        assert!((abundance(&config, 25) - 0.75).abs() < 1e-5);
+        assert_eq!(abundance(&config, 100), 1.0);
+
+        let mut simulation = Simulation::with_config(0, SimulationConfig {
+            food: config,
+            ..Default::default()
+        })
+        .unwrap();
+
+        simulation.steps = 50;
+        simulation.process_food();
+
+        let available = simulation.world.foods().iter().filter(|food| food.is_available()).count();
+        assert_eq!(available, 10);
+    }
+}
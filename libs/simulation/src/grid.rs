@@ -0,0 +1,146 @@
+use crate::*;
+
+// Uniform grid over the world, bucketing indices (e.g. of foods) by position
+// so that finding what's near a point doesn't mean looking at everything.
+// Queries see across the world's edges, since it wraps around (see
+// `process_movement`), and return candidates: everything within the radius,
+// plus possibly a few things a bit further, which callers filter out with
+// their own distance check.
+
+#[derive(Clone, Debug)]
+pub struct SpatialGrid {
+    size: usize,
+    cells: Vec<Vec<usize>>,
+}
+
+impl SpatialGrid {
+    // Keeps sparse worlds with tiny cells from allocating too much
+    const MAX_SIZE: usize = 64;
+
+    // Cells are at least `cell_size` wide; queries are cheapest with a
+    // radius of about that
+
+    pub fn new(cell_size: f32) -> Self {
+        assert!(cell_size > 0.0);
+
+        let size = ((1.0 / cell_size) as usize).clamp(1, Self::MAX_SIZE);
+
+        Self {
+            size,
+            cells: vec![Vec::new(); size * size],
+        }
+    }
+
+    // Indices are positions in `positions`
+
+    pub fn from_positions(cell_size: f32, positions: impl IntoIterator<Item = na::Point2<f32>>) -> Self {
+        let mut grid = Self::new(cell_size);
+
+        for (idx, position) in positions.into_iter().enumerate() {
+            grid.insert(idx, position);
+        }
+
+        grid
+    }
+
+    pub fn insert(&mut self, idx: usize, position: na::Point2<f32>) {
+        let cell = self.cell_of(position);
+        self.cells[cell].push(idx);
+    }
+
+    // `position` is the one `idx` was inserted with
+
+    pub fn remove(&mut self, idx: usize, position: na::Point2<f32>) {
+        let cell = self.cell_of(position);
+        self.cells[cell].retain(|&other| other != idx);
+    }
+
+    pub fn query(&self, position: na::Point2<f32>, radius: f32) -> impl Iterator<Item = usize> + '_ {
+        let size = self.size as isize;
+
+        // Whatever's within `radius` is at most this many cells away
+        let reach = (radius * self.size as f32).ceil() as isize;
+
+        // Every cell at most once, even if the radius spans the whole world
+        let span = (2 * reach + 1).min(size);
+        let (x, y) = self.coords(position);
+        let (x, y) = if span == size { (0, 0) } else { (x - reach, y - reach) };
+
+        (0..span)
+            .flat_map(move |dy| (0..span).map(move |dx| (x + dx, y + dy)))
+            .flat_map(move |(x, y)| self.cells[self.cell(x, y)].iter().copied())
+    }
+
+    fn coords(&self, position: na::Point2<f32>) -> (isize, isize) {
+        let size = self.size as f32;
+
+        (
+            (position.x * size).floor() as isize,
+            (position.y * size).floor() as isize,
+        )
+    }
+
+    fn cell(&self, x: isize, y: isize) -> usize {
+        let size = self.size as isize;
+
+        (y.rem_euclid(size) * size + x.rem_euclid(size)) as usize
+    }
+
+    fn cell_of(&self, position: na::Point2<f32>) -> usize {
+        let (x, y) = self.coords(position);
+        self.cell(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Distance on the torus
+    fn distance(a: na::Point2<f32>, b: na::Point2<f32>) -> f32 {
+        let dx = (a.x - b.x).abs();
+        let dy = (a.y - b.y).abs();
+
+        dx.min(1.0 - dx).hypot(dy.min(1.0 - dy))
+    }
+
+    #[test]
+    fn finds_everything_in_range() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        let positions: Vec<na::Point2<f32>> = (0..500).map(|_| rng.gen()).collect();
+
+        for &(cell_size, radius) in &[(0.01, 0.01), (0.05, 0.25), (0.3, 0.1), (0.1, 0.7)] {
+            let grid = SpatialGrid::from_positions(cell_size, positions.iter().copied());
+
+            // Corners included, so queries have to wrap around
+            let probes = [na::Point2::new(0.0, 0.0), na::Point2::new(0.999, 0.5), rng.gen(), rng.gen()];
+
+            for probe in probes {
+                let mut found: Vec<_> = grid.query(probe, radius).collect();
+                found.sort_unstable();
+
+                assert!(found.windows(2).all(|pair| pair[0] != pair[1]));
+
+                for (idx, position) in positions.iter().enumerate() {
+                    if distance(probe, *position) <= radius {
+                        assert!(found.binary_search(&idx).is_ok());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn follows_moved_items() {
+        let mut grid = SpatialGrid::new(0.1);
+        let (from, to) = (na::Point2::new(0.15, 0.15), na::Point2::new(0.85, 0.85));
+
+        grid.insert(7, from);
+        grid.remove(7, from);
+        grid.insert(7, to);
+
+        assert_eq!(grid.query(from, 0.05).count(), 0);
+        assert_eq!(grid.query(to, 0.05).collect::<Vec<_>>(), vec![7]);
+    }
+}
@@ -1,6 +1,20 @@
-pub use self::{animal::*, brain::*, food::*, world::*, eye::*};
+pub use self::{
+    animal::*, boundary::*, brain::*, checkpoint::*, config::*, fitness::*, food::*, grid::*, obstacle::*, sweep::*,
+    world::*,
+    eye::*,
+};
 mod animal;
+mod boundary;
+mod checkpoint;
+mod config;
+mod evaluation;
+mod fitness;
 mod food;
+mod grid;
+mod obstacle;
+mod open_ended;
+mod predation;
+mod sweep;
 mod world;
 mod eye;
 mod animal_individual;
@@ -10,143 +24,427 @@ use self::animal_individual::*;
 use lib_genetic_algorithm as ga;
 use lib_neural_network as nn;
 use nalgebra as na;
-use rand::{Rng, RngCore};
-use std::{f32::consts::FRAC_PI_2};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
-const SPEED_MIN: f32 = 0.001;
-const SPEED_MAX: f32 = 0.005;
-const SPEED_ACCEL: f32 = 0.2;
-const ROTATION_ACCEL: f32 = FRAC_PI_2;
-const DIST_CAP:f32 = 0.01;
-const GENERATION_LENGTH: usize = 2500;
+// Species of `Simulation::coevolution`
+const PREY: usize = 0;
+const PREDATORS: usize = 1;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Everything random in a simulation comes from its own rng, so a simulation
+// is entirely determined by its seed and config.
 
 pub struct Simulation{
+    seed: u64,
+    rng: ChaCha8Rng,
     world: World,
-    ga: ga::GeneticAlgorithm<ga::RouletteWheelSelection>,
+
+    // Prey and, when there are any, predators, each evolved by its own
+    // genetic algorithm; see `coevolve`
+    coevolution: ga::CoEvolution<ga::RouletteWheelSelection>,
+    config: SimulationConfig,
     age: usize,
+
+    // Steps taken since the beginning, across generations
+    steps: usize,
+    hall_of_fame: ga::HallOfFame,
+    history: Vec<ga::Statistics>,
+    predator_history: Vec<ga::Statistics>,
+
+    // Which predator caught which animal this generation, by their indices
+    catches: Vec<(usize, usize)>,
+
+    // Overrides `config.fitness`, see `with_fitness`
+    fitness: Option<Box<dyn Fitness>>,
 }
 impl Simulation{
+    // Seeded from `rng`; see `seed` to reproduce it
+
     pub fn random(rng: &mut dyn RngCore) -> Self {
-        let world = World::random(rng);
-        
+        Self::from_seed(rng.gen())
+    }
+
+    pub fn from_seed(seed: u64) -> Self {
+        Self::with_config(seed, SimulationConfig::default())
+            .expect("default config is valid")
+    }
+
+    pub fn with_config(seed: u64, config: SimulationConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        Ok(Self {
+            world: World::random(&config, &mut rng),
+            seed,
+            rng,
+            coevolution: Self::coevolution(&config),
+            hall_of_fame: ga::HallOfFame::new(config.evolution.hall_of_fame_size),
+            config,
+            age: 0,
+            steps: 0,
+            history: Vec::new(),
+            predator_history: Vec::new(),
+            catches: Vec::new(),
+            fitness: None,
+        })
+    }
+
+    // Populations are taken from the world whenever they evolve, so they
+    // start out empty
+
+    fn coevolution(config: &SimulationConfig) -> ga::CoEvolution<ga::RouletteWheelSelection> {
+        let coevolution = ga::CoEvolution::new(ga::Pairing::AllVsAll)
+            .with_species(Self::genetic_algorithm(&config.evolution), Vec::new());
+
+        if config.predators.count > 0 {
+            coevolution.with_species(Self::genetic_algorithm(&config.evolution), Vec::new())
+        } else {
+            coevolution
+        }
+    }
+
+    fn genetic_algorithm(
+        config: &EvolutionConfig,
+    ) -> ga::GeneticAlgorithm<ga::RouletteWheelSelection> {
         let ga = ga::GeneticAlgorithm::new(
             ga::RouletteWheelSelection::new(),
             ga::UniformCrossover::new(),
-            ga::GaussianMutation::new(0.01, 0.3),
-        );
-        Self { world, ga, age: 0}
+            ga::BoundedMutation::new(
+                ga::GaussianMutation::new(config.mutation_chance, config.mutation_coeff),
+                ga::GeneBounds::uniform(-config.weight_limit, config.weight_limit, ga::Repair::Clamp),
+            ),
+        )
+        .with_crossover_rate(config.crossover_rate);
+
+        let ga = match config.rate_control {
+            RateControl::Fixed => ga,
+            RateControl::OneFifth { window, factor } => ga.with_rate_controller(ga::OneFifthRule::new(window, factor)),
+            RateControl::Diversity { target, factor } => ga.with_rate_controller(ga::DiversityControl::new(target, factor)),
+        };
+
+        if config.genealogy { ga.with_genealogy() } else { ga }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
     }
 
     pub fn world(&self) -> &World {
         &self.world
     }
 
-    pub fn step(
-        &mut self, 
-        rng: &mut dyn RngCore) -> Option<ga::Statistics> {
-        self.process_collisions(rng);
-        self.process_brains();
-        self.process_movement();
+    // Generations evaluated so far; in `Mode::OpenEnded`, the number of
+    // `generation_length`-step periods statistics have been taken over
 
-        self.age += 1;
+    pub fn generation(&self) -> usize {
+        self.history.len()
+    }
 
-        if self.age > GENERATION_LENGTH {
-            Some(self.evolve(rng))
+    // Statistics of every generation evaluated so far, oldest first
+
+    pub fn history(&self) -> &[ga::Statistics] {
+        &self.history
+    }
+
+    // Statistics of every predator generation, see `PredatorConfig`
+
+    pub fn predator_history(&self) -> &[ga::Statistics] {
+        &self.predator_history
+    }
+
+    // Best animals of all the generations evaluated so far
+
+    pub fn hall_of_fame(&self) -> &ga::HallOfFame {
+        &self.hall_of_fame
+    }
+
+    // Family tree of every animal evolved so far, see `Animal::lineage`;
+    // `None` unless `evolution.genealogy` is set
+
+    pub fn genealogy(&self) -> Option<&ga::Genealogy> {
+        self.coevolution.ga(PREY).genealogy()
+    }
+
+    pub fn best_brain(&self) -> Option<Brain> {
+        let best = self.hall_of_fame.best()?;
+
+        Some(Brain::from_chromosome(best.chromosome.clone(), &Eye::new(&self.config.eye)))
+    }
+
+    pub fn step(&mut self) -> Option<ga::Statistics> {
+        self.tick();
+
+        if self.age > self.config.evolution.generation_length {
+            Some(match self.config.evolution.mode {
+                Mode::Generational => self.evolve(),
+                Mode::OpenEnded => self.census(),
+            })
         }
         else {
             None
         }
     }
 
+    // Moves the world on by one step, without evolving it; returns the
+    // animals that died in it
+
+    fn tick(&mut self) -> Vec<Animal> {
+        self.process_collisions();
+        self.process_predation();
+        self.process_brains();
+        self.process_movement();
+
+        let dead = if self.config.evolution.mode == Mode::OpenEnded {
+            let dead = self.process_metabolism();
+            self.process_reproduction();
+            dead
+        } else {
+            Vec::new()
+        };
+
+        self.process_food();
+
+        self.age += 1;
+        self.steps += 1;
+
+        dead
+    }
+
     // Skips to the end of the current generation
 
-    pub fn train(&mut self, rng: &mut dyn RngCore) -> ga::Statistics {
+    pub fn train(&mut self) -> ga::Statistics {
         loop {
-            if let Some(summary) = self.step(rng) {
+            if let Some(summary) = self.step() {
                 return summary;
             }
         }
     }
 
     fn process_movement(&mut self) {
-        for animal in &mut self.world.animals {
-            animal.position += animal.rotation * na::Vector2::new(0.0, animal.speed);
-            
-            animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
-            animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+        let obstacles = self.config.world.boundary.repeat(&self.config.world.obstacles);
+        let open_ended = self.config.evolution.mode == Mode::OpenEnded;
+
+        let energy = &self.config.energy;
+
+        for animal in self.world.animals.iter_mut().chain(&mut self.world.predators) {
+            let step = animal.rotation * na::Vector2::new(0.0, animal.speed);
+
+            animal.metrics.age += 1;
+            animal.metrics.energy_spent += energy.idle_cost + energy.move_cost * animal.speed;
+
+            // Animals stop in front of obstacles, until they turn away
+            if obstacles.iter().any(|obstacle| obstacle.blocks(animal.position, step)) {
+                continue;
+            }
+
+            animal.metrics.distance += animal.speed;
+
+            if self.config.world.boundary.advance(animal, step) {
+                continue;
+            }
+
+            if open_ended && animal.species == Species::Prey {
+                animal.energy = 0.0;
+            } else {
+                animal.satiation = 0;
+                animal.position = Obstacle::free_position(&self.config.world, &mut self.rng)
+                    .expect("obstacles are validated to leave room");
+            }
         }
     }
-    fn process_collisions(&mut self, rng: &mut dyn RngCore) {
-        for animal in &mut self.world.animals {
-            for food in &mut self.world.foods {
-                let distance = na::distance(
-                    &animal.position,
-                    &food.position,
-                );
-                if distance <= DIST_CAP {
-                    animal.satiation += 1;
-                    food.position = rng.gen::<na::Point2<f32>>();
+    fn process_collisions(&mut self) {
+        let eat_range = self.config.animal.eat_range;
+        let energy = self.config.energy.clone();
+
+        let mut grid = SpatialGrid::new(eat_range);
+
+        for (idx, food) in self.world.foods.iter().enumerate() {
+            if food.is_available() {
+                grid.insert(idx, food.position);
+            }
+        }
+
+        for animal_idx in 0..self.world.animals.len() {
+            let position = self.world.animals[animal_idx].position;
+
+            // In order, so foods are eaten as if every one of them was checked
+            let mut nearby: Vec<_> = grid.query(position, eat_range).collect();
+            nearby.sort_unstable();
+
+            for idx in nearby {
+                let food = &mut self.world.foods[idx];
+
+                let distance = self.config.world.boundary.distance(position, food.position);
+
+                if distance > eat_range {
+                    continue;
+                }
+
+                let nutrition = self.config.food.kinds[food.kind].nutrition;
+                let gain = nutrition as f32 * energy.food;
+
+                // Toxic food can starve animals to death in `Mode::OpenEnded`
+                let animal = &mut self.world.animals[animal_idx];
+                animal.satiation = animal.satiation.saturating_add_signed(nutrition as isize);
+                animal.energy = (animal.energy + gain).min(energy.max);
+
+                if gain >= 0.0 {
+                    animal.metrics.energy_gained += gain;
+                } else {
+                    animal.metrics.energy_lost -= gain;
+                }
+
+                food.bites -= 1;
+
+                if food.bites > 0 {
+                    continue;
+                }
+
+                grid.remove(idx, food.position);
+
+                if self.config.food.regrowth_delay > 0 {
+                    food.regrows_in = self.config.food.regrowth_delay;
+                } else {
+                    self.regrow(idx);
+                    grid.insert(idx, self.world.foods[idx].position);
                 }
             }
         }
     }
 
     fn process_brains(&mut self) {
+        let scene = Scene::of(&self.world, &self.config);
 
-       for animal in &mut self.world.animals {
-                let vision =  animal.eye.process_vision(
+        let species = [
+            (Species::Prey, &mut self.world.animals),
+            (Species::Predator, &mut self.world.predators),
+        ];
+
+        for (species, animals) in species {
+            for (idx, animal) in animals.iter_mut().enumerate() {
+                let vision = animal.eye.perceive(
                     animal.position,
                     animal.rotation,
-                    &self.world.foods,
-                );
-
-                let response = animal.brain.nn.propagate(vision);
-
-                let rel_speed = response[0].clamp(
-                    -SPEED_ACCEL,
-                    SPEED_ACCEL,
-                );
-    
-                let rel_rotation = response[1].clamp(
-                    -ROTATION_ACCEL,
-                    ROTATION_ACCEL,
+                    species,
+                    scene.visible(species, idx, animal.position, animal.eye.reach()),
+                    scene.obstacles(),
                 );
 
-                animal.speed =
-                (animal.speed + rel_speed).clamp(SPEED_MIN, SPEED_MAX);
-
-                animal.rotation = na::Rotation2::new(
-                animal.rotation.angle() + rel_rotation,
-                );
+                animal.steer(vision, &self.config);
+            }
         }
     }
 
-    fn evolve(&mut self, rng: &mut dyn RngCore) -> ga::Statistics{
+    fn evolve(&mut self) -> ga::Statistics{
 
         self.age = 0;
 
-        let current_pop: Vec<_> = self
+        let fitness = self.fitness();
+
+        #[cfg(not(feature = "parallel"))]
+        let mut current_pop: Vec<_> = self
         .world
         .animals
         .iter()
-        .map(AnimalIndividual::from_animal)
+        .map(|animal| AnimalIndividual::from_animal(animal, fitness))
         .collect();
 
-        let (evolved_pop, stats) = self.ga.iterate(
-          &current_pop,
-            rng,
-        );
-
-        self.world.animals = evolved_pop
-        .into_iter()
-        .map(|individual| individual.into_animal(rng))
+        #[cfg(feature = "parallel")]
+        let mut current_pop: Vec<_> = self
+        .world
+        .animals
+        .par_iter()
+        .map(|animal| AnimalIndividual::from_animal(animal, fitness))
         .collect();
 
+        self.coevolution.ga_mut(PREY).assign_lineage(&mut current_pop);
+        self.hall_of_fame.update(&current_pop, self.coevolution.ga(PREY).generation());
+
+        let stats = if self.world.predators.is_empty() {
+            let (evolved_pop, stats) = self.coevolution.ga_mut(PREY).iterate(
+              &current_pop,
+                &mut self.rng,
+            );
+
+            self.world.animals = evolved_pop
+            .into_iter()
+            .map(|individual| individual.into_animal(&self.config, Species::Prey, &mut self.rng))
+            .collect();
+
+            stats
+        } else {
+            self.coevolve(&current_pop)
+        };
 
-        for food in &mut self.world.foods {
-            food.position = rng.gen();
-        }
+
+        self.world.foods = Food::sow(&self.config, &self.world.patches, &mut self.rng);
+
+        self.history.push(stats.clone());
 
         stats
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(seed: u64) -> String {
+        let config = SimulationConfig {
+            evolution: EvolutionConfig {
+                generation_length: 200,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut simulation = Simulation::with_config(seed, config).unwrap();
+
+        for _ in 0..3 {
+            simulation.train();
+        }
+
+        serde_json::to_string(&simulation.checkpoint()).unwrap()
+    }
+
+    #[test]
+    fn same_seed_same_world() {
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42), run(43));
+    }
+
+    #[test]
+    fn rates_adapt_when_asked() {
+        let config = SimulationConfig {
+            evolution: EvolutionConfig {
+                generation_length: 50,
+                rate_control: RateControl::Diversity { target: 0.0, factor: 2.0 },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut simulation = Simulation::with_config(0, config).unwrap();
+
+        simulation.train();
+        simulation.train();
+
+        let rates: Vec<_> = simulation
+            .history()
+            .iter()
+            .map(|stats| stats.rates().unwrap().mutation.unwrap().chance)
+            .collect();
+
+        // Any diversity is above the target, so mutation calms down
+        assert_eq!(rates[0], 0.01);
+        assert!((rates[1] - 0.005).abs() < 1e-6);
+    }
+}
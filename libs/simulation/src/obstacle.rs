@@ -0,0 +1,283 @@
+use crate::*;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+// Something animals can't walk through and food can't grow in, set up in
+// the config:
+//
+//     [[world.obstacles]]
+//     shape = "rect"
+//     min = [0.4, 0.0]
+//     max = [0.6, 0.3]
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "shape", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Obstacle {
+    Circle { center: na::Point2<f32>, radius: f32 },
+
+    // Axis-aligned
+    Rect { min: na::Point2<f32>, max: na::Point2<f32> },
+
+    // A wall with no thickness
+    Segment { from: na::Point2<f32>, to: na::Point2<f32> },
+}
+
+impl Obstacle {
+    pub fn contains(&self, point: na::Point2<f32>) -> bool {
+        match self {
+            Self::Circle { center, radius } => na::distance(center, &point) <= *radius,
+
+            Self::Rect { min, max } => {
+                (min.x..=max.x).contains(&point.x) && (min.y..=max.y).contains(&point.y)
+            }
+
+            Self::Segment { .. } => false,
+        }
+    }
+
+    // Point of the obstacle closest to `point`; `point` itself when it's
+    // inside
+
+    pub fn closest_point(&self, point: na::Point2<f32>) -> na::Point2<f32> {
+        match self {
+            Self::Circle { center, radius } => {
+                let offset = point - center;
+
+                if offset.norm() <= *radius {
+                    point
+                } else {
+                    center + offset.normalize() * *radius
+                }
+            }
+
+            Self::Rect { min, max } => na::Point2::new(
+                point.x.clamp(min.x, max.x),
+                point.y.clamp(min.y, max.y),
+            ),
+
+            Self::Segment { from, to } => {
+                let along = to - from;
+                let t = ((point - from).dot(&along) / along.norm_squared()).clamp(0.0, 1.0);
+
+                from + along * t
+            }
+        }
+    }
+
+    // How far from `origin` a ray going in `direction` (normalized) hits the
+    // obstacle; never from inside of it, so that whatever ends up in there
+    // can always get back out
+
+    pub fn ray_hit(&self, origin: na::Point2<f32>, direction: na::Vector2<f32>) -> Option<f32> {
+        if self.contains(origin) {
+            return None;
+        }
+
+        match self {
+            Self::Circle { center, radius } => {
+                let offset = center - origin;
+                let along = offset.dot(&direction);
+                let off = offset.norm_squared() - along * along;
+
+                if along < 0.0 || off > radius * radius {
+                    return None;
+                }
+
+                Some(along - (radius * radius - off).sqrt())
+            }
+
+            // Slab method: the ray is inside the rect where it's between
+            // both pairs of sides at once
+            Self::Rect { min, max } => {
+                let mut enter = 0.0_f32;
+                let mut exit = f32::INFINITY;
+
+                for axis in 0..2 {
+                    if direction[axis] == 0.0 {
+                        if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                            return None;
+                        }
+
+                        continue;
+                    }
+
+                    let a = (min[axis] - origin[axis]) / direction[axis];
+                    let b = (max[axis] - origin[axis]) / direction[axis];
+
+                    enter = enter.max(a.min(b));
+                    exit = exit.min(a.max(b));
+                }
+
+                (enter <= exit).then_some(enter)
+            }
+
+            Self::Segment { from, to } => {
+                let wall = to - from;
+                let denom = cross(direction, wall);
+
+                if denom == 0.0 {
+                    return None;
+                }
+
+                let offset = from - origin;
+                let t = cross(offset, wall) / denom;
+                let u = cross(offset, direction) / denom;
+
+                (t >= 0.0 && (0.0..=1.0).contains(&u)).then_some(t)
+            }
+        }
+    }
+
+    // Whether moving by `step` from `from` runs into the obstacle
+
+    pub fn blocks(&self, from: na::Point2<f32>, step: na::Vector2<f32>) -> bool {
+        let length = step.norm();
+
+        if length == 0.0 {
+            return false;
+        }
+
+        self.ray_hit(from, step / length)
+            .is_some_and(|dist| dist <= length)
+    }
+
+    pub(crate) fn shifted(&self, by: na::Vector2<f32>) -> Self {
+        match *self {
+            Self::Circle { center, radius } => Self::Circle { center: center + by, radius },
+            Self::Rect { min, max } => Self::Rect { min: min + by, max: max + by },
+            Self::Segment { from, to } => Self::Segment { from: from + by, to: to + by },
+        }
+    }
+
+    pub(crate) fn is_valid(&self) -> bool {
+        match self {
+            Self::Circle { radius, .. } => *radius > 0.0,
+            Self::Rect { min, max } => min.x < max.x && min.y < max.y,
+            Self::Segment { from, to } => from != to,
+        }
+    }
+
+    // Random spot outside of every obstacle. Should random tries keep
+    // landing in obstacles, one of the free cells of a grid laid over the
+    // world is picked instead; fails only when there's none, which configs
+    // are validated against.
+
+    pub(crate) fn free_position(world: &WorldConfig, rng: &mut dyn RngCore) -> Result<na::Point2<f32>, ConfigError> {
+        const ATTEMPTS: usize = 100;
+
+        let obstacles = &world.boundary.repeat(&world.obstacles);
+
+        for _ in 0..ATTEMPTS {
+            let position = rng.gen();
+
+            if !Self::any_contains(obstacles, position) {
+                return Ok(position);
+            }
+        }
+
+        Self::free_cells(obstacles)
+            .choose(rng)
+            .copied()
+            .ok_or(ConfigError::Invalid {
+                field: "world.obstacles",
+                reason: "must leave room for animals and food",
+            })
+    }
+
+    pub(crate) fn leave_room(world: &WorldConfig) -> bool {
+        !Self::free_cells(&world.boundary.repeat(&world.obstacles)).is_empty()
+    }
+
+    // Centers of the cells of a `CELLS`x`CELLS` grid that aren't covered by
+    // any obstacle
+
+    fn free_cells(obstacles: &[Self]) -> Vec<na::Point2<f32>> {
+        const CELLS: usize = 100;
+
+        let center = |idx: usize| (idx as f32 + 0.5) / CELLS as f32;
+
+        (0..CELLS * CELLS)
+            .map(|idx| na::Point2::new(center(idx % CELLS), center(idx / CELLS)))
+            .filter(|&position| !Self::any_contains(obstacles, position))
+            .collect()
+    }
+
+    fn any_contains(obstacles: &[Self], position: na::Point2<f32>) -> bool {
+        obstacles.iter().any(|obstacle| obstacle.contains(position))
+    }
+}
+
+fn cross(a: na::Vector2<f32>, b: na::Vector2<f32>) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f32, y: f32) -> na::Point2<f32> {
+        na::Point2::new(x, y)
+    }
+
+    #[test]
+    fn rays_hit_every_shape() {
+        let right = na::Vector2::x();
+
+        let circle = Obstacle::Circle { center: point(0.5, 0.5), radius: 0.1 };
+        let rect = Obstacle::Rect { min: point(0.4, 0.4), max: point(0.6, 0.6) };
+        let segment = Obstacle::Segment { from: point(0.5, 0.0), to: point(0.5, 1.0) };
+
+        for obstacle in [&circle, &rect] {
+            let hit = obstacle.ray_hit(point(0.1, 0.5), right).unwrap();
+            assert!((hit - 0.3).abs() < 1e-5);
+
+            assert_eq!(obstacle.ray_hit(point(0.1, 0.5), -right), None);
+            assert_eq!(obstacle.ray_hit(point(0.1, 0.9), right), None);
+            assert_eq!(obstacle.ray_hit(point(0.5, 0.5), right), None);
+            assert!(!obstacle.blocks(point(0.5, 0.5), na::Vector2::new(0.02, 0.0)));
+        }
+
+        let hit = segment.ray_hit(point(0.1, 0.5), right).unwrap();
+        assert!((hit - 0.4).abs() < 1e-5);
+        assert_eq!(segment.ray_hit(point(0.1, 0.5), na::Vector2::y()), None);
+    }
+
+    #[test]
+    fn walls_block_movement_through_them() {
+        let wall = Obstacle::Segment { from: point(0.5, 0.0), to: point(0.5, 1.0) };
+
+        assert!(wall.blocks(point(0.49, 0.5), na::Vector2::new(0.02, 0.0)));
+        assert!(!wall.blocks(point(0.47, 0.5), na::Vector2::new(0.02, 0.0)));
+        assert!(!wall.blocks(point(0.49, 0.5), na::Vector2::new(-0.02, 0.0)));
+
+        let closest = wall.closest_point(point(0.2, 1.5));
+        assert_eq!(closest, point(0.5, 1.0));
+    }
+
+    #[test]
+    fn nothing_spawns_inside_obstacles() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let world = |obstacles: Vec<Obstacle>| WorldConfig { obstacles, ..Default::default() };
+
+        let obstacles = world(vec![Obstacle::Rect { min: point(0.0, 0.0), max: point(1.0, 0.9) }]);
+
+        for _ in 0..20 {
+            assert!(Obstacle::free_position(&obstacles, &mut rng).unwrap().y > 0.9);
+        }
+
+        // Too little room for random tries to find, but some nonetheless
+        let obstacles = world(vec![
+            Obstacle::Rect { min: point(0.0, 0.0), max: point(1.0, 0.99) },
+            Obstacle::Rect { min: point(0.0, 0.0), max: point(0.99, 1.0) },
+        ]);
+
+        for _ in 0..20 {
+            let position = Obstacle::free_position(&obstacles, &mut rng).unwrap();
+            assert!(position.x > 0.99 && position.y > 0.99);
+        }
+
+        let obstacles = world(vec![Obstacle::Rect { min: point(-0.1, -0.1), max: point(1.1, 1.1) }]);
+        assert!(Obstacle::free_position(&obstacles, &mut rng).is_err());
+        assert!(!Obstacle::leave_room(&obstacles));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::*;
+use ga::Individual;
+use std::{iter, mem};
+
+// `Mode::OpenEnded`: rather than being replaced all at once, animals die
+// and are born one by one, as their energy allows.
+
+impl Simulation {
+    // Animals pay for staying alive and for how far they move; those out of
+    // energy die and are returned. Should they all die, the world is
+    // restocked with random ones so that the simulation can go on.
+
+    pub(crate) fn process_metabolism(&mut self) -> Vec<Animal> {
+        let energy = &self.config.energy;
+
+        for animal in &mut self.world.animals {
+            animal.energy -= energy.idle_cost + energy.move_cost * animal.speed;
+        }
+
+        let (alive, dead) = mem::take(&mut self.world.animals)
+            .into_iter()
+            .partition(|animal| animal.energy > 0.0);
+
+        self.world.animals = alive;
+
+        if self.world.animals.is_empty() {
+            self.world.animals = (0..self.config.world.animals)
+                .map(|_| Animal::random(&self.config, Species::Prey, &mut self.rng))
+                .collect();
+        }
+
+        dead
+    }
+
+    // Every well-fed animal has a child right where it stands, with the
+    // nearest animal it can see if there's any, and passes it some of its
+    // energy.
+
+    pub(crate) fn process_reproduction(&mut self) {
+        let energy = self.config.energy.clone();
+
+        let ready: Vec<_> = self
+            .world
+            .animals
+            .iter()
+            .enumerate()
+            .filter(|(_, animal)| animal.energy >= energy.reproduction_threshold)
+            .map(|(idx, _)| idx)
+            .collect();
+
+        for idx in ready {
+            if self.world.animals.len() >= energy.max_population {
+                break;
+            }
+
+            let family: Vec<_> = iter::once(idx).chain(self.nearest_mate(idx)).collect();
+            let parents = self.individuals(&family);
+            let parents: Vec<_> = parents.iter().collect();
+
+            let mut child = self
+                .coevolution
+                .ga_mut(PREY)
+                .breed(&parents, &mut self.rng)
+                .into_animal(&self.config, Species::Prey, &mut self.rng);
+
+            let parent = &mut self.world.animals[idx];
+
+            parent.energy -= energy.reproduction_cost;
+            child.position = parent.position;
+            child.energy = energy.reproduction_cost;
+
+            self.world.animals.push(child);
+        }
+    }
+
+    // Takes stock of the population every `generation_length` steps - the
+    // open-ended counterpart of `evolve`. Fitness is measured over an
+    // animal's whole life so far.
+
+    pub(crate) fn census(&mut self) -> ga::Statistics {
+        self.age = 0;
+
+        let everyone: Vec<_> = (0..self.world.animals.len()).collect();
+        let population = self.individuals(&everyone);
+
+        self.hall_of_fame.update(&population, self.history.len());
+
+        let stats = ga::Statistics::new(&population).with_rates(self.coevolution.ga(PREY).rates());
+
+        self.history.push(stats.clone());
+        self.evolve_predators();
+
+        stats
+    }
+
+    fn nearest_mate(&self, idx: usize) -> Option<usize> {
+        let animal = &self.world.animals[idx];
+
+        self.world
+            .animals
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != idx)
+            .map(|(other, mate)| (other, self.config.world.boundary.distance(animal.position, mate.position)))
+            .filter(|(_, distance)| *distance <= self.config.eye.fov_range)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(other, _)| other)
+    }
+
+    // Animals of the initial population only get a lineage once they're
+    // needed, i.e. when they become parents or get counted
+
+    fn individuals(&mut self, animals: &[usize]) -> Vec<AnimalIndividual> {
+        let fitness = self.fitness();
+
+        let mut individuals: Vec<_> = animals
+            .iter()
+            .map(|&idx| AnimalIndividual::from_animal(&self.world.animals[idx], fitness))
+            .collect();
+
+        self.coevolution.ga_mut(PREY).assign_lineage(&mut individuals);
+
+        for (&idx, individual) in animals.iter().zip(&individuals) {
+            self.world.animals[idx].lineage = individual.lineage().cloned();
+        }
+
+        individuals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(energy: EnergyConfig) -> SimulationConfig {
+        SimulationConfig {
+            evolution: EvolutionConfig {
+                mode: Mode::OpenEnded,
+                generation_length: 100,
+                ..Default::default()
+            },
+            energy,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn starving_animals_die_and_are_replaced() {
+        let mut simulation = Simulation::with_config(0, config(EnergyConfig {
+            initial: 0.01,
+            food: 0.0,
+            idle_cost: 0.001,
+            ..Default::default()
+        }))
+        .unwrap();
+
+        let founders: Vec<_> = simulation
+            .world()
+            .animals()
+            .iter()
+            .map(|animal| animal.position())
+            .collect();
+
+        for _ in 0..5 {
+            simulation.step();
+        }
+
+        assert_eq!(simulation.world().animals().len(), 10);
+        assert!(simulation.world().animals().iter().all(|animal| animal.energy() < 0.01));
+
+        // Every animal is drained by at least `idle_cost` a step, so they're
+        // all gone within ten steps - and replaced by newcomers
+        for _ in 0..10 {
+            simulation.step();
+        }
+
+        assert_eq!(simulation.world().animals().len(), 10);
+        assert!(simulation
+            .world()
+            .animals()
+            .iter()
+            .all(|animal| !founders.contains(&animal.position())));
+    }
+
+    #[test]
+    fn well_fed_animals_reproduce() {
+        let mut config = config(EnergyConfig {
+            initial: 2.0,
+            max_population: 15,
+            ..Default::default()
+        });
+        config.evolution.genealogy = true;
+
+        let mut simulation = Simulation::with_config(0, config).unwrap();
+
+        simulation.step();
+
+        // Everyone's ready to reproduce right away, up to the limit
+        let animals = simulation.world().animals();
+        assert_eq!(animals.len(), 15);
+
+        let children: Vec<_> = animals
+            .iter()
+            .filter_map(|animal| animal.lineage())
+            .filter(|lineage| !lineage.parents.is_empty())
+            .collect();
+
+        assert_eq!(children.len(), 5);
+        assert!(children.iter().all(|lineage| lineage.generation == 1));
+
+        // Parents got their lineage as they became parents
+        for child in &children {
+            for parent in &child.parents {
+                assert!(simulation.genealogy().unwrap().get(*parent).is_some());
+            }
+        }
+
+        let stats = simulation.train();
+
+        assert_eq!(simulation.generation(), 1);
+        assert!(stats.max_fitness() >= stats.min_fitness());
+        assert!(!simulation.hall_of_fame().is_empty());
+    }
+}
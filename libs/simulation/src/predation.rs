@@ -0,0 +1,227 @@
+use crate::*;
+use ga::Individual;
+use std::mem;
+
+impl Simulation {
+    // Predators catch every animal within `catch_range`; how that hurts the
+    // animal depends on the mode, see `PredatorConfig::catch_penalty`.
+
+    pub(crate) fn process_predation(&mut self) {
+        let config = &self.config.predators;
+        let lethal = self.config.evolution.mode == Mode::OpenEnded;
+
+        if self.world.predators.is_empty() {
+            return;
+        }
+
+        let mut grid = SpatialGrid::from_positions(
+            config.catch_range,
+            self.world.animals.iter().map(Animal::position),
+        );
+
+        for (predator_idx, predator) in self.world.predators.iter_mut().enumerate() {
+            let mut nearby: Vec<_> = grid.query(predator.position, config.catch_range).collect();
+            nearby.sort_unstable();
+
+            for idx in nearby {
+                let animal = &mut self.world.animals[idx];
+
+                // Already caught this step
+                if lethal && animal.energy <= 0.0 {
+                    continue;
+                }
+
+                if self.config.world.boundary.distance(predator.position, animal.position) > config.catch_range {
+                    continue;
+                }
+
+                predator.satiation += 1;
+
+                if lethal {
+                    animal.energy = 0.0;
+                } else {
+                    animal.satiation = animal.satiation.saturating_sub(config.catch_penalty);
+                    self.catches.push((predator_idx, idx));
+
+                    grid.remove(idx, animal.position);
+                    animal.position = Obstacle::free_position(&self.config.world, &mut self.rng)
+                        .expect("obstacles are validated to leave room");
+                    grid.insert(idx, animal.position);
+                }
+            }
+        }
+    }
+
+    // In `Mode::Generational` both species are replaced at once, scored
+    // against each other: every animal meets every predator, the predator
+    // scoring how many times it caught that animal and the animal keeping
+    // its own fitness. Returns the animals' statistics.
+
+    pub(crate) fn coevolve(&mut self, animals: &[AnimalIndividual]) -> ga::Statistics {
+        let catches = mem::take(&mut self.catches);
+        let predators = self.predator_individuals();
+
+        self.coevolution.set_population(PREY, animals);
+        self.coevolution.set_population(PREDATORS, &predators);
+
+        let mut stats = self.coevolution.step(&mut self.rng, |encounter| {
+            let [animal, predator] = encounter.individuals;
+            let caught = catches.iter().filter(|&&catch| catch == (predator, animal)).count();
+
+            (animals[animal].fitness(), caught as f32)
+        });
+
+        self.world.animals = self.offspring(PREY, Species::Prey);
+        self.world.predators = self.offspring(PREDATORS, Species::Predator);
+
+        self.predator_history.push(stats.remove(PREDATORS));
+        stats.remove(PREY)
+    }
+
+    // In `Mode::OpenEnded` there's no generation of animals to co-evolve
+    // with, so predators are replaced on their own every generation, fitness
+    // being how many animals they've caught
+
+    pub(crate) fn evolve_predators(&mut self) {
+        if self.world.predators.is_empty() {
+            return;
+        }
+
+        let mut population = self.predator_individuals();
+        let ga = self.coevolution.ga_mut(PREDATORS);
+
+        ga.assign_lineage(&mut population);
+
+        let (evolved, stats) = ga.iterate(&population, &mut self.rng);
+
+        self.world.predators = evolved
+            .into_iter()
+            .map(|individual| individual.into_animal(&self.config, Species::Predator, &mut self.rng))
+            .collect();
+
+        self.predator_history.push(stats);
+    }
+
+    pub(crate) fn predator_individuals(&self) -> Vec<AnimalIndividual> {
+        let catches = |predator: &Animal| predator.satiation as f32;
+
+        self.world
+            .predators
+            .iter()
+            .map(|predator| AnimalIndividual::from_animal(predator, &catches))
+            .collect()
+    }
+
+    // The new generation of `species`, straight out of `coevolution`
+    fn offspring(&mut self, species: usize, kind: Species) -> Vec<Animal> {
+        let population = self.coevolution.population(species);
+        let lineages = self.coevolution.lineages(species);
+
+        population
+            .iter()
+            .zip(lineages)
+            .map(|(chromosome, lineage)| {
+                Animal::from_chromosome(&self.config, kind, chromosome.clone(), lineage.clone(), &mut self.rng)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulation(mode: Mode) -> Simulation {
+        let config = SimulationConfig {
+            evolution: EvolutionConfig {
+                mode,
+                generation_length: 100,
+                ..Default::default()
+            },
+            predators: PredatorConfig {
+                count: 3,
+                catch_penalty: 2,
+                ..Default::default()
+            },
+            eye: EyeConfig {
+                channels: vec![Channel::Food, Channel::Predators],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        Simulation::with_config(0, config).unwrap()
+    }
+
+    // Puts the first predator right on top of the first animal
+    fn ambush(simulation: &mut Simulation) {
+        let position = simulation.world.animals[0].position;
+        simulation.world.predators[0].position = position;
+    }
+
+    #[test]
+    fn caught_animals_lose_satiation() {
+        let mut simulation = simulation(Mode::Generational);
+
+        simulation.world.animals[0].satiation = 5;
+        ambush(&mut simulation);
+        simulation.process_predation();
+
+        let animal = &simulation.world.animals[0];
+        assert_eq!(animal.satiation, 3);
+        assert!(simulation.world.predators[0].position != animal.position);
+        assert_eq!(simulation.world.predators[0].satiation, 1);
+    }
+
+    #[test]
+    fn predators_are_scored_by_the_animals_they_caught() {
+        let mut simulation = simulation(Mode::Generational);
+
+        ambush(&mut simulation);
+        simulation.process_predation();
+
+        assert_eq!(simulation.catches, vec![(0, 0)]);
+
+        let animals: Vec<_> = simulation
+            .world
+            .animals
+            .iter()
+            .map(|animal| AnimalIndividual::from_animal(animal, &|_: &Animal| 1.0))
+            .collect();
+
+        simulation.coevolve(&animals);
+
+        // One catch out of the 10 animals the predator met
+        assert_eq!(simulation.predator_history()[0].max_fitness(), 0.1);
+        assert!(simulation.catches.is_empty());
+    }
+
+    #[test]
+    fn caught_animals_die_in_open_ended_mode() {
+        let mut simulation = simulation(Mode::OpenEnded);
+
+        ambush(&mut simulation);
+        simulation.step();
+
+        assert_eq!(simulation.world().animals().len(), 9);
+        assert_eq!(simulation.world().predators()[0].satiation, 1);
+    }
+
+    #[test]
+    fn predators_evolve_alongside_animals() {
+        let mut simulation = simulation(Mode::Generational);
+
+        assert_eq!(simulation.world().predators()[0].species(), Species::Predator);
+
+        simulation.train();
+
+        assert_eq!(simulation.generation(), 1);
+        assert_eq!(simulation.predator_history().len(), 1);
+        assert_eq!(simulation.world().predators().len(), 3);
+        assert!(simulation
+            .world()
+            .predators()
+            .iter()
+            .all(|predator| predator.lineage().is_some()));
+    }
+}
@@ -0,0 +1,342 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+// Values to try for every hyperparameter; each combination is run once per
+// seed, headless, for `generations` generations. Everything else comes from
+// `base`.
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Sweep {
+    pub base: SimulationConfig,
+    pub mutation_chance: Vec<f32>,
+    pub mutation_coeff: Vec<f32>,
+    pub crossover_rate: Vec<f32>,
+    pub generation_length: Vec<usize>,
+    pub seeds: Vec<u64>,
+    pub generations: usize,
+}
+
+impl Sweep {
+    // Starts off with just the default of every hyperparameter and a single
+    // seed
+
+    pub fn new(generations: usize) -> Self {
+        let base = SimulationConfig::default();
+        let defaults = base.evolution.clone();
+
+        Self {
+            base,
+            mutation_chance: vec![defaults.mutation_chance],
+            mutation_coeff: vec![defaults.mutation_coeff],
+            crossover_rate: vec![defaults.crossover_rate],
+            generation_length: vec![defaults.generation_length],
+            seeds: vec![0],
+            generations,
+        }
+    }
+
+    // Also resets the values to try to the ones of `base`
+
+    pub fn with_base(self, base: SimulationConfig) -> Self {
+        let evolution = &base.evolution;
+
+        Self {
+            mutation_chance: vec![evolution.mutation_chance],
+            mutation_coeff: vec![evolution.mutation_coeff],
+            crossover_rate: vec![evolution.crossover_rate],
+            generation_length: vec![evolution.generation_length],
+            base,
+            ..self
+        }
+    }
+
+    pub fn with_mutation_chance(mut self, values: Vec<f32>) -> Self {
+        self.mutation_chance = values;
+        self
+    }
+
+    pub fn with_mutation_coeff(mut self, values: Vec<f32>) -> Self {
+        self.mutation_coeff = values;
+        self
+    }
+
+    pub fn with_crossover_rate(mut self, values: Vec<f32>) -> Self {
+        self.crossover_rate = values;
+        self
+    }
+
+    pub fn with_generation_length(mut self, values: Vec<usize>) -> Self {
+        self.generation_length = values;
+        self
+    }
+
+    pub fn with_seeds(mut self, seeds: Vec<u64>) -> Self {
+        self.seeds = seeds;
+        self
+    }
+
+    // Every combination of the given values
+
+    pub fn grid(&self) -> Vec<EvolutionConfig> {
+        let mut grid = Vec::new();
+
+        for &mutation_chance in &self.mutation_chance {
+            for &mutation_coeff in &self.mutation_coeff {
+                for &crossover_rate in &self.crossover_rate {
+                    for &generation_length in &self.generation_length {
+                        grid.push(EvolutionConfig {
+                            mutation_chance,
+                            mutation_coeff,
+                            crossover_rate,
+                            generation_length,
+                            ..self.base.evolution.clone()
+                        });
+                    }
+                }
+            }
+        }
+
+        grid
+    }
+
+    // `samples` combinations drawn uniformly between the smallest and the
+    // largest value given for each hyperparameter
+
+    pub fn random(&self, samples: usize, rng: &mut dyn RngCore) -> Vec<EvolutionConfig> {
+        fn span<T: PartialOrd + Copy>(values: &[T]) -> (T, T) {
+            assert!(!values.is_empty());
+
+            values.iter().fold((values[0], values[0]), |(min, max), &value| {
+                (
+                    if value < min { value } else { min },
+                    if value > max { value } else { max },
+                )
+            })
+        }
+
+        let (chance_min, chance_max) = span(&self.mutation_chance);
+        let (coeff_min, coeff_max) = span(&self.mutation_coeff);
+        let (rate_min, rate_max) = span(&self.crossover_rate);
+        let (length_min, length_max) = span(&self.generation_length);
+
+        (0..samples)
+            .map(|_| EvolutionConfig {
+                mutation_chance: rng.gen_range(chance_min..=chance_max),
+                mutation_coeff: rng.gen_range(coeff_min..=coeff_max),
+                crossover_rate: rng.gen_range(rate_min..=rate_max),
+                generation_length: rng.gen_range(length_min..=length_max),
+                ..self.base.evolution.clone()
+            })
+            .collect()
+    }
+
+    // Runs every configuration with every seed; configurations come out
+    // best first. Fails without running anything if any of them is invalid.
+
+    pub fn run(&self, configurations: &[EvolutionConfig]) -> Result<SweepReport, ConfigError> {
+        assert!(!self.seeds.is_empty());
+        assert!(self.generations > 0);
+
+        let configs: Vec<_> = configurations
+            .iter()
+            .map(|evolution| {
+                let config = SimulationConfig {
+                    evolution: evolution.clone(),
+                    ..self.base.clone()
+                };
+
+                config.validate().map(|_| config)
+            })
+            .collect::<Result<_, _>>()?;
+
+        #[cfg(not(feature = "parallel"))]
+        let results = configs
+            .iter()
+            .map(|config| self.evaluate(config))
+            .collect();
+
+        #[cfg(feature = "parallel")]
+        let results = configs
+            .par_iter()
+            .map(|config| self.evaluate(config))
+            .collect();
+
+        let mut report = SweepReport { results };
+        report.rank();
+        Ok(report)
+    }
+
+    fn evaluate(&self, config: &SimulationConfig) -> SweepResult {
+        let runs: Vec<Vec<ga::Statistics>> = self
+            .seeds
+            .iter()
+            .map(|&seed| {
+                let mut simulation = Simulation::with_config(seed, config.clone())
+                    .expect("config has been validated");
+
+                (0..self.generations)
+                    .map(|_| simulation.train())
+                    .collect()
+            })
+            .collect();
+
+        let mean = |value: fn(&ga::Statistics) -> f32| {
+            runs.iter()
+                .map(|history| value(history.last().unwrap()))
+                .sum::<f32>() / runs.len() as f32
+        };
+
+        let best_fitness = runs
+            .iter()
+            .flatten()
+            .map(ga::Statistics::max_fitness)
+            .fold(f32::NEG_INFINITY, f32::max);
+
+        SweepResult {
+            evolution: config.evolution.clone(),
+            runs: runs.len(),
+            avg_fitness: mean(ga::Statistics::avg_fitness),
+            max_fitness: mean(ga::Statistics::max_fitness),
+            best_fitness,
+        }
+    }
+}
+
+// How one configuration did, averaged over its runs
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SweepResult {
+    pub evolution: EvolutionConfig,
+    pub runs: usize,
+
+    // Average and best fitness of the last generation, averaged over runs
+    pub avg_fitness: f32,
+    pub max_fitness: f32,
+
+    // Best fitness any run reached in any generation
+    pub best_fitness: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SweepReport {
+    pub results: Vec<SweepResult>,
+}
+
+impl SweepReport {
+    // Configurations are ranked by the average fitness they end up with,
+    // ties broken by the best fitness
+
+    fn rank(&mut self) {
+        self.results.sort_by(|a, b| {
+            b.avg_fitness
+                .total_cmp(&a.avg_fitness)
+                .then(b.max_fitness.total_cmp(&a.max_fitness))
+        });
+    }
+
+    pub fn best(&self) -> Option<&SweepResult> {
+        self.results.first()
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "rank,mutation_chance,mutation_coeff,crossover_rate,generation_length,runs,avg_fitness,max_fitness,best_fitness\n",
+        );
+
+        for (rank, result) in self.results.iter().enumerate() {
+            let params = &result.evolution;
+
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{}",
+                rank + 1,
+                params.mutation_chance,
+                params.mutation_coeff,
+                params.crossover_rate,
+                params.generation_length,
+                result.runs,
+                result.avg_fitness,
+                result.max_fitness,
+                result.best_fitness,
+            );
+        }
+
+        csv
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("report is always serializable")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sweep() -> Sweep {
+        Sweep::new(2)
+            .with_mutation_chance(vec![0.01, 0.1])
+            .with_mutation_coeff(vec![0.3])
+            .with_crossover_rate(vec![0.5, 1.0])
+            .with_generation_length(vec![20, 40])
+            .with_seeds(vec![1, 2])
+    }
+
+    #[test]
+    fn grid_covers_every_combination() {
+        let grid = sweep().grid();
+
+        assert_eq!(grid.len(), 8);
+        assert!(grid.contains(&EvolutionConfig {
+            mutation_chance: 0.1,
+            mutation_coeff: 0.3,
+            crossover_rate: 0.5,
+            generation_length: 40,
+            ..EvolutionConfig::default()
+        }));
+    }
+
+    #[test]
+    fn random_search_stays_within_the_given_values() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        for params in sweep().random(20, &mut rng) {
+            assert!((0.01..=0.1).contains(&params.mutation_chance));
+            assert_eq!(params.mutation_coeff, 0.3);
+            assert!((0.5..=1.0).contains(&params.crossover_rate));
+            assert!((20..=40).contains(&params.generation_length));
+        }
+    }
+
+    #[test]
+    fn report_ranks_configurations() {
+        let sweep = sweep();
+        let report = sweep.run(&sweep.grid()).unwrap();
+
+        assert_eq!(report.results.len(), 8);
+        assert!(report.results.iter().all(|result| result.runs == 2));
+
+        assert!(report
+            .results
+            .windows(2)
+            .all(|pair| pair[0].avg_fitness >= pair[1].avg_fitness));
+
+        let csv = report.to_csv();
+        assert_eq!(csv.lines().count(), 9);
+        assert!(csv.lines().nth(1).unwrap().starts_with("1,"));
+
+        let json: SweepReport = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json, report);
+    }
+
+    #[test]
+    fn invalid_configurations_are_rejected() {
+        let sweep = sweep().with_mutation_chance(vec![0.5, 2.0]);
+
+        assert!(matches!(
+            sweep.run(&sweep.grid()),
+            Err(ConfigError::Invalid { field: "evolution.mutation_chance", .. }),
+        ));
+    }
+}
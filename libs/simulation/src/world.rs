@@ -4,23 +4,36 @@ use crate::*;
 pub struct World {
     pub(crate) animals: Vec<Animal>,
     pub(crate) foods: Vec<Food>,
+    pub(crate) predators: Vec<Animal>,
+
+    // Where food grows, for `FoodSpawn::Patches`
+    pub(crate) patches: Vec<na::Point2<f32>>,
 }
 
 
 impl World {
-    pub fn random(rng:&mut dyn RngCore) -> Self {
-        const NUM_OF_ANIMALS: usize = 10;
-        const NUM_OF_FOOD: usize = 20;
-
-        let animals = (0..NUM_OF_ANIMALS)
-            .map(|_| Animal::random(rng))
+    pub fn random(config: &SimulationConfig, rng: &mut dyn RngCore) -> Self {
+        let animals = (0..config.world.animals)
+            .map(|_| Animal::random(config, Species::Prey, rng))
             .collect();
 
-        let foods = (0..NUM_OF_FOOD)
-            .map(|_| Food::random(rng))
+        let patches = match config.food.spawn {
+            FoodSpawn::Patches { count, .. } => (0..count)
+                .map(|_| {
+                    Obstacle::free_position(&config.world, rng)
+                        .expect("obstacles are validated to leave room")
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let foods = Food::sow(config, &patches, rng);
+
+        let predators = (0..config.predators.count)
+            .map(|_| Animal::random(config, Species::Predator, rng))
             .collect();
 
-        Self { animals, foods }
+        Self { animals, foods, predators, patches }
     }
 
     pub fn animals(&self) -> &[Animal] {
@@ -29,4 +42,81 @@ impl World {
     pub fn foods(&self) -> &[Food] {
         &self.foods
     }
-}
\ No newline at end of file
+    pub fn predators(&self) -> &[Animal] {
+        &self.predators
+    }
+    pub fn patches(&self) -> &[na::Point2<f32>] {
+        &self.patches
+    }
+}
+
+// Everything an eye can see, gathered once per step so that animals can
+// look around while being moved
+
+pub(crate) struct Scene {
+    // Available foods, then animals, then predators
+    entities: Vec<(Entity, na::Point2<f32>)>,
+    prey: usize,
+    predators: usize,
+    grid: SpatialGrid,
+    boundary: Boundary,
+
+    // See `Boundary::repeat`
+    obstacles: Vec<Obstacle>,
+}
+
+impl Scene {
+    pub(crate) fn of(world: &World, config: &SimulationConfig) -> Self {
+        let entities: Vec<_> = world
+            .foods
+            .iter()
+            .filter(|food| food.is_available())
+            .map(|food| (Entity::Food(food.kind), food.position))
+            .chain(world.animals.iter().map(|animal| (Entity::Animal(Species::Prey), animal.position)))
+            .chain(world.predators.iter().map(|predator| (Entity::Animal(Species::Predator), predator.position)))
+            .collect();
+
+        let foods = world.foods.iter().filter(|food| food.is_available()).count();
+        let cell_size = config.eye.fov_range.max(config.predators.eye.fov_range);
+
+        Self {
+            grid: SpatialGrid::from_positions(cell_size, entities.iter().map(|(_, position)| *position)),
+            entities,
+            prey: foods,
+            predators: foods + world.animals.len(),
+            boundary: config.world.boundary,
+            obstacles: config.world.boundary.repeat(&config.world.obstacles),
+        }
+    }
+
+    // Everything within `range` of the `idx`-th animal of `species`, which
+    // is at `position`; possibly along with a few things a bit further.
+    // Across a wrapping edge, things are moved to where they appear to be
+    // from `position`.
+
+    pub(crate) fn visible(
+        &self,
+        species: Species,
+        idx: usize,
+        position: na::Point2<f32>,
+        range: f32,
+    ) -> impl Iterator<Item = (Entity, na::Point2<f32>)> + '_ {
+        let own = match species {
+            Species::Prey => self.prey + idx,
+            Species::Predator => self.predators + idx,
+        };
+
+        self.grid
+            .query(position, range)
+            .filter(move |&other| other != own)
+            .map(move |other| {
+                let (entity, target) = self.entities[other];
+
+                (entity, position + self.boundary.offset(position, target))
+            })
+    }
+
+    pub(crate) fn obstacles(&self) -> &[Obstacle] {
+        &self.obstacles
+    }
+}
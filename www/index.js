@@ -23,7 +23,7 @@ context.scale(viewportScale, viewportScale);
 context.fillStyle = 'rgb(0, 0, 0)';
 
 CanvasRenderingContext2D.prototype.drawTriangle = 
-    function (x, y, size, rotation ) {
+    function (x, y, size, rotation, color = 'rgb(255, 255, 255)') {
         const interiorAng = 2.0/3.0 * Math.PI;
         const doubleAng = 2.0 * interiorAng;
 
@@ -49,20 +49,60 @@ CanvasRenderingContext2D.prototype.drawTriangle =
         );
         
 
-        this.fillStyle = 'rgb(255, 255, 255)'; // A nice white color
+        this.fillStyle = color;
         this.fill();
         this.stroke();
 };
 
 CanvasRenderingContext2D.prototype.drawCircle = 
-    function(x,y, radius) {
+    function(x,y, radius, color = 'rgb(0, 150, 0)') {
         this.beginPath();
 
         this.arc(x,y, radius, 0, 2.0 * Math.PI);
 
-        this.fillStyle = 'rgb(0, 150, 0)';
+        this.fillStyle = color;
         this.fill();
 };
+CanvasRenderingContext2D.prototype.drawObstacle =
+    function(obstacle) {
+        this.beginPath();
+
+        switch (obstacle.shape) {
+            case 'circle':
+                this.arc(
+                    obstacle.center[0] * viewportWidth,
+                    obstacle.center[1] * viewportHeight,
+                    obstacle.radius * viewportWidth,
+                    0,
+                    2.0 * Math.PI,
+                );
+                break;
+
+            case 'rect':
+                this.rect(
+                    obstacle.min[0] * viewportWidth,
+                    obstacle.min[1] * viewportHeight,
+                    (obstacle.max[0] - obstacle.min[0]) * viewportWidth,
+                    (obstacle.max[1] - obstacle.min[1]) * viewportHeight,
+                );
+                break;
+
+            case 'segment':
+                this.moveTo(obstacle.from[0] * viewportWidth, obstacle.from[1] * viewportHeight);
+                this.lineTo(obstacle.to[0] * viewportWidth, obstacle.to[1] * viewportHeight);
+                break;
+        }
+
+        this.fillStyle = 'rgb(90, 90, 90)';
+        this.strokeStyle = 'rgb(90, 90, 90)';
+        this.fill();
+        this.stroke();
+        this.strokeStyle = 'rgb(0, 0, 0)';
+};
+
+// One per food kind, in the order of the config's `[[food.kinds]]`
+const foodColors = ['rgb(0, 150, 0)', 'rgb(160, 0, 160)', 'rgb(220, 160, 0)', 'rgb(0, 120, 200)'];
+
 function redraw() {
         context.clearRect(0, 0, viewportWidth, viewportHeight);
 
@@ -72,6 +112,10 @@ function redraw() {
         const world = simulation.world();
         simulation.step();
 
+        for (const obstacle of world.obstacles) {
+            context.drawObstacle(obstacle);
+        }
+
         for (const animal of simulation.world().animals) {
             context.drawTriangle(
                 animal.x * viewportWidth,
@@ -81,6 +125,16 @@ function redraw() {
             );
         }
 
+        for (const predator of simulation.world().predators) {
+            context.drawTriangle(
+                predator.x * viewportWidth,
+                predator.y * viewportHeight,
+                scale * viewportWidth,
+                predator.rotation,
+                'rgb(200, 30, 30)',
+            );
+        }
+
         
         requestAnimationFrame(redraw);
 
@@ -89,6 +143,7 @@ function redraw() {
                 food.x * viewportWidth,
                 food.y * viewportHeight,
                 ratio * viewportWidth,
+                foodColors[food.kind % foodColors.length],
             );
         }
 }